@@ -0,0 +1,98 @@
+//! Coût de la phase d'émission des champs sur une grille 512x512.
+//!
+//! Compare le recalcul complet des champs (chaque émetteur repeint sa zone
+//! à chaque pas) avec un pas complet de l'engine, qui ne met à jour que les
+//! sources ayant bougé ou changé de valeur.
+//!
+//! Lancer avec `cargo bench --bench fields`.
+
+use OSCARv2::dsl::compile::compile;
+use OSCARv2::dsl::parser::parse_file;
+use OSCARv2::engine::engine::Engine;
+use OSCARv2::model::builder::WorldBuilder;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const WORLD: &str = "
+world 512 512 FFF
+seed 1
+
+mineral sun FF0
+var light 255 # couvre toute la grille
+field light -1
+
+mineral stone 777
+var glow 4
+field glow -1 euclid gauss
+
+animal ant 000
+var scent 2
+field scent -1
+var smell
+sensor smell scent 1
+
+agent sun (256,256)
+agent stone (0:512:8,0:512:8)
+agent ant (4:512:64,4:512:64)
+";
+
+const TICKS: u32 = 20;
+
+fn engine() -> Engine {
+    let config = parse_file(WORLD).expect("Failed to parse DSL");
+    let species_defs = compile(&config).expect("Failed to compile DSL");
+    let mut builder = WorldBuilder::from_config(&config).expect("Failed to build world");
+    builder.place_agents(&config).expect("Failed to place agents");
+    let (mut world, agents, rng) = builder.build_with_rng();
+    world.agents = agents;
+    // aucun capteur ne lit `light` ni `glow` : on les calcule comme pour
+    // un affichage
+    Engine::new(world, species_defs)
+        .with_rng(rng)
+        .with_rendered_fields(&["light", "glow"])
+}
+
+/// Ancien fonctionnement : tous les champs sont vidés puis repeints
+fn full_recompute(engine: &mut Engine) {
+    for field in engine.fields.values_mut() {
+        field.clear();
+    }
+    for agent in engine.world.agents.iter().filter(|a| a.alive) {
+        let spec = &engine.species_defs[agent.species_id];
+        for fdef in &spec.field_defs {
+            let value = agent.get_var(spec.var_index(&fdef.name).expect("field variable"));
+            if let Some(field) = engine.fields.get_mut(&fdef.name) {
+                field.add_source(agent.pos, value);
+            }
+        }
+    }
+}
+
+fn per_tick(mut run: impl FnMut()) -> Duration {
+    run(); // premier pas : toutes les sources sont posées
+    let start = Instant::now();
+    for _ in 0..TICKS {
+        run();
+    }
+    start.elapsed() / TICKS
+}
+
+fn main() {
+    let mut full = engine();
+    let emitters = full.world.agents.iter().filter(|a| a.alive).count();
+    let full_time = per_tick(|| {
+        full_recompute(&mut full);
+        black_box(&full.fields);
+    });
+
+    let mut incremental = engine();
+    let step_time = per_tick(|| {
+        incremental.step();
+        black_box(&incremental.fields);
+    });
+
+    println!("512x512, {} emitters, {} ticks", emitters, TICKS);
+    println!("full field recompute    : {:>10.3} ms/tick", full_time.as_secs_f64() * 1e3);
+    println!("engine step (all phases): {:>10.3} ms/tick", step_time.as_secs_f64() * 1e3);
+    println!("speedup                 : {:>10.1}x", full_time.as_secs_f64() / step_time.as_secs_f64());
+}
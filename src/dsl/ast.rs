@@ -21,14 +21,67 @@ pub struct VarDef {
 
 #[derive(Debug)]
 pub enum Value {
-    Int(i32),
-    Str(String),
+    Int(i32),    // valeur numérique : `var seed 3 -1`
+    Str(String), // valeur reprise du statut précédent : `var grass grass -1`
+}
+
+/// Opérateur de comparaison d'une condition `var op threshold`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Less,     // <
+    Greater,  // >
+    Equal,    // ==
+    NotEqual, // !=
+}
+
+impl CompareOp {
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "<" => Some(CompareOp::Less),
+            ">" => Some(CompareOp::Greater),
+            "==" => Some(CompareOp::Equal),
+            "!=" => Some(CompareOp::NotEqual),
+            _ => None,
+        }
+    }
+
+    /// Évalue `value op threshold`
+    pub fn eval(self, value: i32, threshold: i32) -> bool {
+        match self {
+            CompareOp::Less => value < threshold,
+            CompareOp::Greater => value > threshold,
+            CompareOp::Equal => value == threshold,
+            CompareOp::NotEqual => value != threshold,
+        }
+    }
+}
+
+/// Mode de mise à jour des agents (DSL `mode sync|async`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UpdateMode {
+    /// Chaque phase lit l'état de la phase précédente pour tous les agents
+    /// (automates cellulaires : Life, Wireworld)
+    #[default]
+    Sync,
+    /// Les agents sont traités un par un dans un ordre aléatoire et voient
+    /// les changements de ceux passés avant eux (Sugarscape)
+    Async,
+}
+
+impl UpdateMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sync" => Some(UpdateMode::Sync),
+            "async" => Some(UpdateMode::Async),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct StatusRule {
     pub variable: Option<String>,
-    pub less_than: Option<bool>,
+    pub op: Option<CompareOp>,
     pub threshold: Option<i32>,
     pub new_status: String,
 }
@@ -36,15 +89,80 @@ pub struct StatusRule {
 #[derive(Debug, Clone)]
 pub struct BirthRule {
     pub variable: Option<String>,
-    pub less_than: Option<bool>,
+    pub op: Option<CompareOp>,
     pub threshold: Option<i32>,
     pub child_status: String,
 }
 
+/// Distance utilisée pour propager un champ (DSL `field name step metric`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Metric {
+    /// max(|dr|, |dc|) : le champ forme un carré
+    #[default]
+    Chebyshev,
+    /// |dr| + |dc| : le champ forme un losange
+    Manhattan,
+    /// distance à vol d'oiseau : le champ forme un disque
+    Euclidean,
+}
+
+impl Metric {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "chebyshev" => Some(Metric::Chebyshev),
+            "manhattan" => Some(Metric::Manhattan),
+            "euclid" => Some(Metric::Euclidean),
+            _ => None,
+        }
+    }
+}
+
+/// Profil de décroissance d'un champ avec la distance
+/// (DSL `field name step metric decay`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Decay {
+    /// perd `step` par pas de distance
+    #[default]
+    Linear,
+    /// décroissance exponentielle
+    Exponential,
+    /// cloche de Gauss
+    Gaussian,
+    /// en 1/d² loin de la source
+    InverseSquare,
+}
+
+impl Decay {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "linear" => Some(Decay::Linear),
+            "exp" => Some(Decay::Exponential),
+            "gauss" => Some(Decay::Gaussian),
+            "inverse-square" => Some(Decay::InverseSquare),
+            _ => None,
+        }
+    }
+}
+
+/// Durée de vie d'un champ (DSL `field name step ... persist diffusion evaporation`)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FieldKind {
+    /// Recalculé à chaque pas à partir des seuls agents présents
+    #[default]
+    Instant,
+    /// Garde en mémoire ce que les agents y ont déposé : à chaque pas,
+    /// chaque case cède la fraction `diffusion` de sa valeur à ses voisines
+    /// puis perd la fraction `evaporation` de ce qu'il lui reste
+    Persistent { diffusion: f32, evaporation: f32 },
+}
+
 #[derive(Debug)]
 pub struct FieldDef {
     pub name: String,
     pub step: i32,
+    pub metric: Metric,
+    pub decay: Decay,
+    pub kind: FieldKind,
 }
 
 #[derive(Debug)]
@@ -69,6 +187,7 @@ pub struct Species {
     pub births: Vec<BirthRule>,
     pub fields: Vec<FieldDef>,
     pub sensors: Vec<SensorDef>,
+    pub blocks: Vec<String>, // champs arrêtés par les agents de cette espèce
 }
 
 #[derive(Debug)]
@@ -82,4 +201,6 @@ pub struct ConfigAst {
     pub world: Option<World>,
     pub species: Vec<Species>,
     pub agents: Vec<AgentDef>,
+    pub seed: Option<u64>,        // graine de la simulation (DSL `seed 42`)
+    pub mode: Option<UpdateMode>, // mode de mise à jour (DSL `mode async`)
 }
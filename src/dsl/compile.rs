@@ -0,0 +1,217 @@
+use crate::dsl::ast::{self, ConfigAst, Value};
+use crate::engine::engine::{SpeciesDef, VarInit, VarSpec};
+use crate::engine::field::FieldDef;
+use crate::engine::rules::{BirthRule, Condition, StatusRule, BUILTIN_STATUSES};
+use crate::engine::sensor::{SensorDef, SensorTerm};
+use crate::model::agent::SpeciesKind;
+use crate::model::builder::parse_color;
+use anyhow::{Result, anyhow};
+use std::collections::{HashMap, HashSet};
+
+/// Compile l'AST en définitions d'espèces pour l'engine.
+///
+/// L'index d'une espèce dans le vecteur retourné est son `species_id`,
+/// dans l'ordre de déclaration du fichier DSL.
+pub fn compile(config: &ConfigAst) -> Result<Vec<SpeciesDef>> {
+    let field_table = collect_fields(config)?;
+    let statuses: HashSet<&str> = config.species.iter()
+        .map(|sp| sp.name.as_str())
+        .chain(BUILTIN_STATUSES)
+        .collect();
+    let var_names: HashSet<&str> = config.species.iter()
+        .flat_map(|sp| &sp.vars)
+        .map(|v| v.name.as_str())
+        .collect();
+
+    let mut seen = HashMap::new();
+    let mut defs = Vec::with_capacity(config.species.len());
+    for (species_id, species) in config.species.iter().enumerate() {
+        if seen.insert(species.name.as_str(), species_id).is_some() {
+            return Err(anyhow!("Species '{}' declared twice", species.name));
+        }
+        defs.push(compile_species(species_id, species, &field_table, &statuses, &var_names)?);
+    }
+    Ok(defs)
+}
+
+/// Construit la table globale des champs : un champ peut être émis par
+/// plusieurs espèces, mais toujours avec le même pas, la même métrique,
+/// le même profil et la même persistance.
+///
+/// Dans le DSL, le pas est la variation du champ à chaque pas de distance :
+/// `field flame -1` perd 1 par case, `field light 0` couvre toute la grille.
+/// Un pas positif est refusé. L'engine reçoit la décroissance, positive.
+fn collect_fields(config: &ConfigAst) -> Result<HashMap<String, FieldDef>> {
+    let mut table: HashMap<String, FieldDef> = HashMap::new();
+    for species in &config.species {
+        for field in &species.fields {
+            if field.step > 0 {
+                return Err(anyhow!(
+                    "Field '{}' of species '{}' has positive step {}: field steps must be negative or 0",
+                    field.name, species.name, field.step
+                ));
+            }
+            if let ast::FieldKind::Persistent { diffusion, evaporation } = field.kind {
+                if !(0.0..=1.0).contains(&diffusion) || !(0.0..=1.0).contains(&evaporation) {
+                    return Err(anyhow!(
+                        "Field '{}' of species '{}' has persistence rates {} and {}: rates must lie between 0 and 1",
+                        field.name, species.name, diffusion, evaporation
+                    ));
+                }
+            }
+            let def = FieldDef {
+                name: field.name.clone(),
+                step: -field.step,
+                metric: field.metric,
+                decay: field.decay,
+                kind: field.kind,
+            };
+            match table.get(&field.name) {
+                Some(existing) if existing.step != def.step => {
+                    return Err(anyhow!(
+                        "Field '{}' declared with steps {} and {}",
+                        field.name, -existing.step, field.step
+                    ));
+                }
+                Some(existing) if existing.metric != def.metric || existing.decay != def.decay => {
+                    return Err(anyhow!(
+                        "Field '{}' declared with profiles {:?}/{:?} and {:?}/{:?}",
+                        field.name, existing.metric, existing.decay, def.metric, def.decay
+                    ));
+                }
+                Some(existing) if existing.kind != def.kind => {
+                    return Err(anyhow!(
+                        "Field '{}' declared with kinds {:?} and {:?}",
+                        field.name, existing.kind, def.kind
+                    ));
+                }
+                Some(_) => {}
+                None => { table.insert(field.name.clone(), def); }
+            }
+        }
+    }
+    Ok(table)
+}
+
+fn compile_species(
+    species_id: usize,
+    species: &ast::Species,
+    field_table: &HashMap<String, FieldDef>,
+    statuses: &HashSet<&str>,
+    var_names: &HashSet<&str>,
+) -> Result<SpeciesDef> {
+    let kind = match species.kind {
+        ast::SpeciesKind::Mineral => SpeciesKind::Mineral,
+        ast::SpeciesKind::Vegetal => SpeciesKind::Vegetal,
+        ast::SpeciesKind::Animal => SpeciesKind::Animal,
+    };
+
+    // une valeur symbolique reprend une variable de l'espèce précédente,
+    // qui doit donc être déclarée par au moins une espèce
+    let mut vars: Vec<VarSpec> = Vec::with_capacity(species.vars.len());
+    for v in &species.vars {
+        let init = match &v.init_value {
+            Value::Int(n) => VarInit::Value(*n),
+            Value::Str(name) if var_names.contains(name.as_str()) => VarInit::Inherit(name.clone()),
+            Value::Str(name) => return Err(anyhow!(
+                "Variable '{}' of species '{}' inherits undeclared variable '{}'",
+                v.name, species.name, name
+            )),
+        };
+        vars.push(VarSpec { name: v.name.clone(), init, timestep: v.timestep });
+    }
+    let var_index = |name: &str| vars.iter().position(|v| v.name == name);
+
+    // un agent émet un champ avec la variable de même nom
+    let mut field_defs = Vec::with_capacity(species.fields.len());
+    for field in &species.fields {
+        if var_index(&field.name).is_none() {
+            return Err(anyhow!(
+                "Field '{}' of species '{}' has no matching variable",
+                field.name, species.name
+            ));
+        }
+        field_defs.push(field_table[&field.name].clone());
+    }
+
+    let mut sensors = Vec::with_capacity(species.sensors.len());
+    for sensor in &species.sensors {
+        let target_var = var_index(&sensor.name).ok_or_else(|| anyhow!(
+            "Sensor variable '{}' not declared in species '{}'",
+            sensor.name, species.name
+        ))?;
+        let mut terms = Vec::with_capacity(sensor.fields.len());
+        for sf in &sensor.fields {
+            if !field_table.contains_key(&sf.field) {
+                return Err(anyhow!(
+                    "Sensor '{}' of species '{}' reads unknown field '{}'",
+                    sensor.name, species.name, sf.field
+                ));
+            }
+            terms.push(SensorTerm { field_name: sf.field.clone(), weight: sf.sensitivity });
+        }
+        sensors.push(SensorDef { name: sensor.name.clone(), target_var, terms });
+    }
+
+    let mut status_rules = Vec::with_capacity(species.statuses.len());
+    for rule in &species.statuses {
+        if !statuses.contains(rule.new_status.as_str()) {
+            return Err(anyhow!(
+                "Species '{}' switches to unknown status '{}'",
+                species.name, rule.new_status
+            ));
+        }
+        status_rules.push(StatusRule {
+            condition: compile_condition(species, &vars, &rule.variable, rule.op, rule.threshold)?,
+            new_status: rule.new_status.clone(),
+        });
+    }
+
+    let mut birth_rules = Vec::with_capacity(species.births.len());
+    for rule in &species.births {
+        birth_rules.push(BirthRule {
+            condition: compile_condition(species, &vars, &rule.variable, rule.op, rule.threshold)?,
+            child_status: rule.child_status.clone(),
+        });
+    }
+
+    for field in &species.blocks {
+        if !field_table.contains_key(field) {
+            return Err(anyhow!(
+                "Species '{}' blocks unknown field '{}'",
+                species.name, field
+            ));
+        }
+    }
+
+    Ok(SpeciesDef {
+        species_id,
+        name: species.name.clone(),
+        kind,
+        color: parse_color(&species.color)?,
+        vars,
+        status_rules,
+        birth_rules,
+        field_defs,
+        sensors,
+        blocks: species.blocks.clone(),
+    })
+}
+
+/// Résout la variable d'une condition `var op threshold` en index
+fn compile_condition(
+    species: &ast::Species,
+    vars: &[VarSpec],
+    variable: &Option<String>,
+    op: Option<ast::CompareOp>,
+    threshold: Option<i32>,
+) -> Result<Option<Condition>> {
+    let (Some(name), Some(threshold)) = (variable, threshold) else {
+        return Ok(None);
+    };
+    let var = vars.iter().position(|v| &v.name == name).ok_or_else(|| anyhow!(
+        "Rule variable '{}' not declared in species '{}'",
+        name, species.name
+    ))?;
+    Ok(Some(Condition { var, op: op.unwrap_or(ast::CompareOp::Equal), threshold }))
+}
@@ -1,4 +1,5 @@
 pub mod ast;
+pub mod compile;
 pub mod parser;
 pub mod token;
 pub mod tokenizer;
\ No newline at end of file
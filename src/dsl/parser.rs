@@ -9,8 +9,9 @@ pub fn parse_file(input: &str) -> Result<ConfigAst> {
 }
 
 pub fn parse_tokens(tokens: &[Token]) -> Result<ConfigAst> {
-    let mut config = ConfigAst { world: None, species: vec![], agents: vec![] };
+    let mut config = ConfigAst { world: None, species: vec![], agents: vec![], seed: None, mode: None };
     let mut current_species: Option<Species> = None;
+    let mut birth_children: Vec<(String, usize)> = vec![];
 
     let mut i = 0;
     while i < tokens.len() {
@@ -35,6 +36,30 @@ pub fn parse_tokens(tokens: &[Token]) -> Result<ConfigAst> {
                     return Err(anyhow!("Invalid world syntax at line {}", tokens[i].line));
                 }
             }
+            TokenKind::Ident(word) if word == "seed" => {
+                // seed value
+                if let Some(TokenKind::Number(seed)) = tokens.get(i+1).map(|t| &t.kind) {
+                    if *seed < 0 {
+                        return Err(anyhow!("Seed must be a non-negative integer at line {}", tokens[i].line));
+                    }
+                    config.seed = Some(*seed as u64);
+                    i += 2;
+                } else {
+                    return Err(anyhow!("Expected integer seed at line {}", tokens[i].line));
+                }
+            }
+            TokenKind::Ident(word) if word == "mode" => {
+                // mode sync|async
+                let mode = match tokens.get(i+1).map(|t| &t.kind) {
+                    Some(TokenKind::Ident(name)) => UpdateMode::from_name(name),
+                    _ => None,
+                };
+                let Some(mode) = mode else {
+                    return Err(anyhow!("Expected update mode 'sync' or 'async' at line {}", tokens[i].line));
+                };
+                config.mode = Some(mode);
+                i += 2;
+            }
             TokenKind::Ident(word) if word == "mineral" || word == "vegetal" || word == "animal" => {
                 if let Some(sp) = current_species.take() {
                     config.species.push(sp);
@@ -58,6 +83,7 @@ pub fn parse_tokens(tokens: &[Token]) -> Result<ConfigAst> {
                 current_species = Some(Species {
                     kind, name, color,
                     vars: vec![], statuses: vec![], births: vec![], fields: vec![], sensors: vec![],
+                    blocks: vec![],
                 });
                 i += 3;
             }
@@ -70,17 +96,29 @@ pub fn parse_tokens(tokens: &[Token]) -> Result<ConfigAst> {
                     s.clone()
                 } else { return Err(anyhow!("Expected variable name after var")); };
                 
-                // For now, just handle simple var name without values
+                // valeur initiale : un nombre, ou le nom d'une variable
+                // dont la valeur est reprise du statut précédent
+                let mut j = i + 2;
+                let init_value = match tokens.get(j).map(|t| &t.kind) {
+                    Some(TokenKind::Number(n)) => { j += 1; Value::Int(*n) }
+                    Some(TokenKind::Ident(s)) => { j += 1; Value::Str(s.clone()) }
+                    _ => Value::Int(0),
+                };
+                let timestep = match tokens.get(j).map(|t| &t.kind) {
+                    Some(TokenKind::Number(n)) => { j += 1; *n }
+                    _ => 0,
+                };
+
                 let var_def = VarDef {
                     name,
-                    init_value: Value::Int(0),
-                    timestep: 0,
+                    init_value,
+                    timestep,
                 };
-                
+
                 if let Some(ref mut species) = current_species {
                     species.vars.push(var_def);
                 }
-                i += 2; // Skip "var" and name
+                i = j;
             }
             TokenKind::Ident(word) if word == "status" => {
                 // status [var condition] new_status
@@ -88,43 +126,141 @@ pub fn parse_tokens(tokens: &[Token]) -> Result<ConfigAst> {
                     return Err(anyhow!("status outside of species at line {}", tokens[i].line));
                 }
                 
-                // Simple case: just "status new_status"
-                let new_status = if let TokenKind::Ident(s) = &tokens[i+1].kind {
+                // status new_status | status var op threshold new_status
+                let (condition, j) = parse_condition(tokens, i + 1)?;
+                let new_status = if let Some(TokenKind::Ident(s)) = tokens.get(j).map(|t| &t.kind) {
                     s.clone()
-                } else { return Err(anyhow!("Expected status name")); };
-                
+                } else { return Err(anyhow!("Expected status name at line {}", tokens[i].line)); };
+
+                let (variable, op, threshold) = match condition {
+                    Some((var, op, threshold)) => (Some(var), Some(op), Some(threshold)),
+                    None => (None, None, None),
+                };
                 let status_rule = StatusRule {
-                    variable: None,
-                    less_than: None,
-                    threshold: None,
+                    variable,
+                    op,
+                    threshold,
                     new_status,
                 };
-                
+
                 if let Some(ref mut species) = current_species {
                     species.statuses.push(status_rule);
                 }
-                i += 2;
+                i = j + 1;
+            }
+            TokenKind::Ident(word) if word == "birth" => {
+                // birth [var condition] child_status
+                if current_species.is_none() {
+                    return Err(anyhow!("birth outside of species at line {}", tokens[i].line));
+                }
+
+                let (condition, j) = parse_condition(tokens, i + 1)?;
+                let child_status = if let Some(TokenKind::Ident(s)) = tokens.get(j).map(|t| &t.kind) {
+                    s.clone()
+                } else { return Err(anyhow!("Expected child status at line {}", tokens[i].line)); };
+
+                let (variable, op, threshold) = match condition {
+                    Some((var, op, threshold)) => (Some(var), Some(op), Some(threshold)),
+                    None => (None, None, None),
+                };
+                // l'espèce enfant peut être déclarée plus loin dans le fichier
+                birth_children.push((child_status.clone(), tokens[i].line));
+                let birth_rule = BirthRule {
+                    variable,
+                    op,
+                    threshold,
+                    child_status,
+                };
+
+                if let Some(ref mut species) = current_species {
+                    species.births.push(birth_rule);
+                }
+                i = j + 1;
             }
             TokenKind::Ident(word) if word == "sensor" => {
                 // sensor name field sensitivity
                 if current_species.is_none() {
                     return Err(anyhow!("sensor outside of species at line {}", tokens[i].line));
                 }
-                i += 1; // Skip for now
+                let line = tokens[i].line;
+                let (name, field) = match (tokens.get(i+1).map(|t| &t.kind), tokens.get(i+2).map(|t| &t.kind)) {
+                    (Some(TokenKind::Ident(n)), Some(TokenKind::Ident(f))) => (n.clone(), f.clone()),
+                    _ => return Err(anyhow!("Expected sensor variable and field at line {}", line)),
+                };
+                let sensitivity = match tokens.get(i+3).map(|t| &t.kind) {
+                    Some(TokenKind::Number(n)) => *n as f32,
+                    Some(TokenKind::Float(f)) => *f,
+                    _ => return Err(anyhow!("Expected sensor weight at line {}", line)),
+                };
+
+                // plusieurs lignes sur la même variable forment un seul capteur
+                if let Some(ref mut species) = current_species {
+                    let sensor_field = SensorField { field, sensitivity };
+                    match species.sensors.iter_mut().find(|s| s.name == name) {
+                        Some(sensor) => sensor.fields.push(sensor_field),
+                        None => species.sensors.push(SensorDef { name, fields: vec![sensor_field] }),
+                    }
+                }
+                i += 4;
             }
             TokenKind::Ident(word) if word == "field" => {
-                // field name step
+                // field name step [metric] [decay] [persist diffusion evaporation]
                 if current_species.is_none() {
                     return Err(anyhow!("field outside of species at line {}", tokens[i].line));
                 }
-                i += 1; // Skip for now
+                let line = tokens[i].line;
+                let name = if let Some(TokenKind::Ident(s)) = tokens.get(i+1).map(|t| &t.kind) {
+                    s.clone()
+                } else { return Err(anyhow!("Expected field name at line {}", line)); };
+                let step = if let Some(TokenKind::Number(n)) = tokens.get(i+2).map(|t| &t.kind) {
+                    *n
+                } else { return Err(anyhow!("Expected integer field step at line {}", line)); };
+                i += 3;
+
+                // métrique, profil et persistance, tous facultatifs
+                let mut metric = Metric::default();
+                let mut decay = Decay::default();
+                let mut kind = FieldKind::default();
+                while let Some(TokenKind::Ident(s)) = tokens.get(i).map(|t| &t.kind) {
+                    if let Some(m) = Metric::from_name(s) {
+                        metric = m;
+                        i += 1;
+                    } else if let Some(d) = Decay::from_name(s) {
+                        decay = d;
+                        i += 1;
+                    } else if s == "persist" {
+                        let rate = |token: Option<&Token>| match token.map(|t| &t.kind) {
+                            Some(TokenKind::Number(n)) => Some(*n as f32),
+                            Some(TokenKind::Float(f)) => Some(*f),
+                            _ => None,
+                        };
+                        let (Some(diffusion), Some(evaporation)) = (rate(tokens.get(i+1)), rate(tokens.get(i+2))) else {
+                            return Err(anyhow!("Expected diffusion and evaporation rates after persist at line {}", line));
+                        };
+                        kind = FieldKind::Persistent { diffusion, evaporation };
+                        i += 3;
+                    } else {
+                        return Err(anyhow!("Unknown field option '{}' at line {}", s, line));
+                    }
+                }
+
+                if let Some(ref mut species) = current_species {
+                    species.fields.push(FieldDef { name, step, metric, decay, kind });
+                }
             }
             TokenKind::Ident(word) if word == "block" => {
                 // block field
                 if current_species.is_none() {
                     return Err(anyhow!("block outside of species at line {}", tokens[i].line));
                 }
-                i += 1; // Skip for now
+                let field = if let Some(TokenKind::Ident(s)) = tokens.get(i+1).map(|t| &t.kind) {
+                    s.clone()
+                } else { return Err(anyhow!("Expected field name after block at line {}", tokens[i].line)); };
+
+                if let Some(ref mut species) = current_species {
+                    species.blocks.push(field);
+                }
+                i += 2;
             }
             TokenKind::Ident(word) if word == "agent" => {
                 // agent species_pattern position1 position2 ...
@@ -212,5 +348,36 @@ pub fn parse_tokens(tokens: &[Token]) -> Result<ConfigAst> {
         config.species.push(sp);
     }
 
+    for (child, line) in birth_children {
+        if !config.species.iter().any(|sp| sp.name == child) {
+            return Err(anyhow!("Unknown child status '{}' in birth rule at line {}", child, line));
+        }
+    }
+
     Ok(config)
 }
+
+/// Condition `var op threshold` d'une règle
+type Condition = (String, CompareOp, i32);
+
+/// Lit une condition optionnelle `var op threshold` à partir de `start`.
+/// Retourne la condition et l'index du premier token qui la suit.
+fn parse_condition(tokens: &[Token], start: usize) -> Result<(Option<Condition>, usize)> {
+    let op = match tokens.get(start + 1).map(|t| &t.kind) {
+        Some(TokenKind::Symbol(sym)) => CompareOp::from_symbol(sym),
+        _ => None,
+    };
+    let Some(op) = op else {
+        return Ok((None, start));
+    };
+
+    let line = tokens[start].line;
+    let variable = if let TokenKind::Ident(s) = &tokens[start].kind {
+        s.clone()
+    } else { return Err(anyhow!("Expected variable name in condition at line {}", line)); };
+    let threshold = if let Some(TokenKind::Number(n)) = tokens.get(start + 2).map(|t| &t.kind) {
+        *n
+    } else { return Err(anyhow!("Expected integer threshold in condition at line {}", line)); };
+
+    Ok((Some((variable, op, threshold)), start + 3))
+}
@@ -16,7 +16,7 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>> {
                 Ok(n) => Token { kind: TokenKind::Number(n), line: line_num + 1 },
                 Err(_) => match word.parse::<f32>() {
                     Ok(f) => Token { kind: TokenKind::Float(f), line: line_num + 1 },
-                    Err(_) => if word == "<" || word == ">" || word == ":" {
+                    Err(_) => if matches!(word, "<" | ">" | "==" | "!=" | ":") {
                         Token { kind: TokenKind::Symbol(word.to_string()), line: line_num + 1 }
                     } else {
                         Token { kind: TokenKind::Ident(word.to_string()), line: line_num + 1 }
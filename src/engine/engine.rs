@@ -1,63 +1,616 @@
-use crate::engine::field::Field;
+use crate::dsl::ast::UpdateMode;
+use crate::engine::field::{Field, FieldDef};
+use crate::engine::movement::{self, MoveIntent, MovePolicy};
+use crate::engine::rules::{self, StatusChange};
 use crate::engine::sensor::SensorDef;
-use crate::model::agent::SpeciesKind;
+use crate::model::agent::{Agent, SpeciesKind};
+use crate::model::position::Position;
 use crate::model::world::World;
-use std::collections::HashMap;
+use crate::utils::log;
+use anyhow::{Result, anyhow};
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet};
+
+/// Variable d'une espèce compilée (issue du DSL `var`)
+#[derive(Debug, Clone)]
+pub struct VarSpec {
+    pub name: String,
+    pub init: VarInit, // valeur initiale
+    pub timestep: i32, // TimeStepValue, ajouté à chaque step
+}
+
+/// Valeur initiale d'une variable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VarInit {
+    /// Valeur fixe : `var seed 3 -1`
+    Value(i32),
+    /// Valeur de la variable nommée dans le statut précédent : `var grass grass -1`
+    Inherit(String),
+}
 
 /// Définit une espèce compilée depuis le DSL
 #[derive(Debug, Clone)]
 pub struct SpeciesDef {
+    pub species_id: usize,            // index dans `Engine::species_defs`
+    pub name: String,                 // nom de l'espèce, aussi nom du statut
     pub kind: SpeciesKind,
     pub color: u32,
-    pub num_vars: usize,
+    pub vars: Vec<VarSpec>,           // variables, dans l'ordre des index de l'agent
     pub status_rules: Vec<crate::engine::rules::StatusRule>,
     pub birth_rules: Vec<crate::engine::rules::BirthRule>,
     pub field_defs: Vec<crate::engine::field::FieldDef>, // définitions des champs
     pub sensors: Vec<SensorDef>,      // capteurs liés aux agents de cette espèce
+    pub blocks: Vec<String>,          // champs arrêtés par les agents de cette espèce
+}
+
+impl SpeciesDef {
+    /// Nombre de variables d'un agent de cette espèce
+    pub fn num_vars(&self) -> usize {
+        self.vars.len()
+    }
+
+    /// Index d'une variable à partir de son nom
+    pub fn var_index(&self, name: &str) -> Option<usize> {
+        self.vars.iter().position(|v| v.name == name)
+    }
+
+    /// Valeurs initiales des variables d'un agent qui entre dans cette espèce.
+    /// `previous` est l'espèce quittée et ses valeurs : les variables `Inherit`
+    /// y reprennent la variable de même nom, ou 0 si elle n'existe pas.
+    pub fn initial_vars(&self, previous: Option<(&SpeciesDef, &[i32])>) -> Vec<i32> {
+        self.vars.iter().map(|v| match &v.init {
+            VarInit::Value(n) => *n,
+            VarInit::Inherit(name) => previous
+                .and_then(|(spec, vals)| spec.var_index(name).and_then(|i| vals.get(i).copied()))
+                .unwrap_or(0),
+        }).collect()
+    }
+
+    /// Lit une variable d'un agent de cette espèce par son nom.
+    /// Seule l'espèce connaît la table des noms : l'agent ne garde que les valeurs.
+    pub fn get_var_by_name(&self, agent: &Agent, name: &str) -> Option<i32> {
+        self.var_index(name).and_then(|idx| agent.vars.get(idx).copied())
+    }
+
+    /// Modifie une variable d'un agent de cette espèce par son nom
+    pub fn set_var_by_name(&self, agent: &mut Agent, name: &str, val: i32) -> Result<()> {
+        let idx = self.var_index(name).ok_or_else(|| anyhow!(
+            "Variable '{}' not declared in species '{}'",
+            name, self.name
+        ))?;
+        agent.set_var(idx, val);
+        Ok(())
+    }
 }
 
+/// Source émise par un agent : (index du champ dans `field_names`, position, valeur)
+type Emission = (usize, Position, i32);
+
 /// L'engine de simulation
 pub struct Engine {
     pub world: World,
     pub species_defs: Vec<SpeciesDef>,
-    pub fields: HashMap<String, Field>, // tous les champs du monde
+    pub fields: HashMap<String, Field>, // champs lus par un capteur ou affichés
     pub running: bool,
+    pub rng: StdRng, // unique source d'aléa de la simulation
+    pub move_policy: MovePolicy,
+    pub update_mode: UpdateMode,
+    emitted: Vec<Vec<Emission>>, // sources posées dans les champs, par agent
+    spare: Vec<Emission>,        // tampon réutilisé pour les sources d'un agent
+    field_names: Vec<String>,    // champs calculés, par index
+    emitters: Vec<Vec<(usize, usize)>>, // par espèce : (index du champ, variable émise)
+    skipped: HashMap<String, FieldDef>, // champs que rien ne lit, jamais calculés
 }
 
 impl Engine {
     pub fn new(world: World, species_defs: Vec<SpeciesDef>) -> Self {
-        // construire la map de tous les champs uniques
+        // construire la map des champs uniques, sans ceux que rien ne lit
+        let used = used_fields(&species_defs);
         let mut fields = HashMap::new();
+        let mut skipped = HashMap::new();
         for spec in &species_defs {
             for f in &spec.field_defs {
-                fields.insert(f.name.clone(), Field::new(f.clone(), world.rows, world.cols));
+                if used.contains(f.name.as_str()) {
+                    fields.insert(f.name.clone(), Field::new(f.clone(), world.rows, world.cols));
+                } else {
+                    skipped.insert(f.name.clone(), f.clone());
+                }
+            }
+        }
+        let mut names: Vec<&String> = skipped.keys().collect();
+        names.sort();
+        for name in names {
+            log::debug(&format!("Field '{}' is never read: skipped", name));
+        }
+
+        let mut engine = Self {
+            world,
+            species_defs,
+            fields,
+            running: true,
+            rng: StdRng::from_os_rng(),
+            move_policy: MovePolicy::default(),
+            update_mode: UpdateMode::default(),
+            emitted: Vec::new(),
+            spare: Vec::new(),
+            field_names: Vec::new(),
+            emitters: Vec::new(),
+            skipped,
+        };
+        engine.index_fields();
+        engine
+    }
+
+    /// Numérote les champs calculés et, pour chaque espèce, les champs
+    /// qu'elle émet avec la variable correspondante
+    fn index_fields(&mut self) {
+        self.field_names = self.fields.keys().cloned().collect();
+        self.field_names.sort();
+        self.emitters = self.species_defs.iter().map(|spec| {
+            spec.field_defs.iter()
+                .filter_map(|fdef| {
+                    let slot = self.field_names.iter().position(|n| n == &fdef.name)?;
+                    Some((slot, spec.var_index(&fdef.name)?))
+                })
+                .collect()
+        }).collect();
+    }
+
+    /// Reprend le générateur du `WorldBuilder` pour une exécution reproductible
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// Fixe la graine du générateur de l'engine
+    pub fn with_seed(self, seed: u64) -> Self {
+        self.with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    /// Choisit l'arbitrage des déplacements concurrents
+    pub fn with_move_policy(mut self, policy: MovePolicy) -> Self {
+        self.move_policy = policy;
+        self
+    }
+
+    /// Choisit le mode de mise à jour des agents
+    pub fn with_update_mode(mut self, mode: UpdateMode) -> Self {
+        self.update_mode = mode;
+        self
+    }
+
+    /// Calcule aussi ces champs, même si aucun capteur ne les lit, pour
+    /// qu'ils puissent être affichés. À appeler avant le premier pas.
+    pub fn with_rendered_fields(mut self, names: &[&str]) -> Self {
+        for name in names {
+            if let Some(def) = self.skipped.remove(*name) {
+                let field = Field::new(def, self.world.rows, self.world.cols);
+                self.fields.insert(name.to_string(), field);
             }
         }
-        Self { world, species_defs, fields, running: true }
+        self.index_fields();
+        self
     }
 
     /// Exécute une étape de simulation
+    ///
+    /// Les phases s'enchaînent toujours dans cet ordre :
+    /// 1. émission des champs à partir des variables des agents
+    /// 2. évaluation des capteurs (lecture des champs émis en 1)
+    /// 3. absorption des champs entre agents voisins
+    /// 4. application des pas de temps des variables
+    /// 5. règles de statut
+    /// 6. déplacement des animaux
+    /// 7. règles de naissance
+    ///
+    /// En mode `Sync`, chaque phase est appliquée à tous les agents avant la
+    /// suivante : les capteurs lisent les champs de l'état précédent, les
+    /// absorptions sont calculées sur les variables du début de phase et les
+    /// déplacements sont décidés sur la grille du début de phase. En mode
+    /// `Async`, les phases 2 à 7 sont enchaînées agent par agent dans un
+    /// ordre aléatoire, et les champs sont mis à jour après chaque agent.
     pub fn step(&mut self) {
         if !self.running { return; }
 
-        // 1) réinitialiser champs
-        for f in self.fields.values_mut() {
-            f.clear();
+        self.emit_fields();
+        match self.update_mode {
+            UpdateMode::Sync => self.step_sync(),
+            UpdateMode::Async => self.step_async(),
+        }
+    }
+
+    fn step_sync(&mut self) {
+        let alive = self.alive_agents();
+        for &idx in &alive {
+            self.update_sensors(idx);
+        }
+        self.absorb_all(&alive);
+        let mut births = Vec::new();
+        for &idx in &alive {
+            self.apply_timesteps(idx);
+            births.extend(self.select_birth(idx));
+            self.apply_status_rules(idx);
+        }
+        self.move_animals();
+        self.place_births(births);
+    }
+
+    fn step_async(&mut self) {
+        let mut order = self.alive_agents();
+        order.shuffle(&mut self.rng);
+
+        let mut births = Vec::new();
+        for idx in order {
+            // un agent peut avoir disparu à cause d'un agent traité avant lui
+            if !self.world.agents[idx].alive { continue; }
+
+            self.update_sensors(idx);
+            for prey in self.absorb(idx) {
+                self.refresh_emissions(prey);
+            }
+            self.apply_timesteps(idx);
+            births.extend(self.select_birth(idx));
+            self.apply_status_rules(idx);
+            if self.world.agents[idx].alive && self.world.agents[idx].species == SpeciesKind::Animal {
+                if let Some((target, _)) = self.best_free_neighbour(idx, true) {
+                    self.world.move_agent(idx, target);
+                }
+            }
+
+            // les agents suivants lisent les champs à jour
+            self.refresh_emissions(idx);
+        }
+        self.place_births(births);
+    }
+
+    fn alive_agents(&self) -> Vec<usize> {
+        self.world.agents.iter().filter(|a| a.alive).map(|a| a.id).collect()
+    }
+
+    /// Phase 1 : met à jour les champs à partir des agents émetteurs.
+    /// Un agent émet un champ avec la valeur de sa variable de même nom.
+    /// Les agents dont l'espèce déclare `block` arrêtent la propagation.
+    ///
+    /// Les sources de chaque agent sont mémorisées d'un pas à l'autre : seules
+    /// celles qui ont bougé ou changé de valeur sont retirées puis reposées,
+    /// un émetteur immobile ne coûte rien. Un champ dont les obstacles ont
+    /// changé est recalculé entièrement, de même qu'un champ persistant qui
+    /// repart de sa mémoire. Les sources mémorisées servent aussi aux
+    /// capteurs pour retirer la contribution de l'agent lui-même.
+    fn emit_fields(&mut self) {
+        let mut obstacles: HashMap<&str, Vec<Position>> = HashMap::new();
+        for agent in self.world.agents.iter().filter(|a| a.alive) {
+            let Some(spec) = self.species_defs.get(agent.species_id) else { continue };
+            for name in &spec.blocks {
+                obstacles.entry(name.as_str()).or_default().push(agent.pos);
+            }
+        }
+
+        let mut rebuilt = vec![false; self.field_names.len()];
+        for (slot, name) in self.field_names.iter().enumerate() {
+            let Some(field) = self.fields.get_mut(name) else { continue };
+            let cells = obstacles.get(name.as_str()).map_or(&[][..], Vec::as_slice);
+            let moved = !field.obstacles_match(cells);
+            if moved || field.is_persistent() {
+                field.tick();
+                rebuilt[slot] = true;
+            }
+            if moved {
+                field.clear_obstacles();
+                for &pos in cells {
+                    field.block(pos);
+                }
+            }
+        }
+
+        if self.emitted.len() < self.world.agents.len() {
+            self.emitted.resize(self.world.agents.len(), vec![]);
+        }
+        for idx in 0..self.world.agents.len() {
+            self.update_emissions(idx, &rebuilt);
+        }
+    }
+
+    /// Remplace dans les champs les sources mémorisées d'un agent par ses
+    /// sources actuelles, si elles ont changé
+    fn refresh_emissions(&mut self, idx: usize) {
+        if self.emitted.len() <= idx {
+            self.emitted.resize(idx + 1, vec![]);
+        }
+        self.update_emissions(idx, &[]);
+    }
+
+    /// Retire les sources mémorisées de l'agent qui ont changé et pose les
+    /// nouvelles. Les champs marqués dans `rebuilt` ont été vidés : on y pose
+    /// toutes les sources actuelles sans rien retirer. Les tampons des
+    /// sources sont réutilisés d'un pas à l'autre.
+    fn update_emissions(&mut self, idx: usize, rebuilt: &[bool]) {
+        let mut now = std::mem::take(&mut self.spare);
+        self.fill_emissions(idx, &mut now);
+        let is_rebuilt = |slot: usize| rebuilt.get(slot).copied().unwrap_or(false);
+
+        if now != self.emitted[idx] || rebuilt.iter().any(|&r| r) {
+            for i in 0..self.emitted[idx].len() {
+                let e = self.emitted[idx][i];
+                if !is_rebuilt(e.0) && !now.contains(&e) {
+                    self.stamp(e, false);
+                }
+            }
+            for &e in &now {
+                if is_rebuilt(e.0) || !self.emitted[idx].contains(&e) {
+                    self.stamp(e, true);
+                }
+            }
+            std::mem::swap(&mut self.emitted[idx], &mut now);
+        }
+        self.spare = now;
+    }
+
+    /// Contribution des sources mémorisées d'un agent au champ `name`, en `at`
+    fn own_contribution(&self, idx: usize, at: Position, name: &str) -> i32 {
+        let Some(emitted) = self.emitted.get(idx) else { return 0 };
+        emitted.iter()
+            .filter(|(slot, _, _)| self.field_names[*slot] == name)
+            .map(|&(_, pos, value)| self.fields[name].contribution(pos, value, at))
+            .sum()
+    }
+
+    /// Sources émises par un agent dans les champs calculés, écrites dans `out`
+    fn fill_emissions(&self, idx: usize, out: &mut Vec<Emission>) {
+        out.clear();
+        let agent = &self.world.agents[idx];
+        if !agent.alive { return; }
+        let Some(emitters) = self.emitters.get(agent.species_id) else { return };
+        out.extend(emitters.iter().map(|&(slot, var_idx)| (slot, agent.pos, agent.get_var(var_idx))));
+    }
+
+    fn stamp(&mut self, (slot, pos, value): Emission, add: bool) {
+        if let Some(field) = self.fields.get_mut(&self.field_names[slot]) {
+            if add {
+                field.add_source(pos, value);
+            } else {
+                field.remove_source(pos, value);
+            }
+        }
+    }
+
+    /// Phase 2 : chaque capteur écrit sa valeur dans sa variable cible
+    /// L'agent ne perçoit pas sa propre émission.
+    fn update_sensors(&mut self, idx: usize) {
+        let pos = self.world.agents[idx].pos;
+        let Some(spec) = self.species_defs.get(self.world.agents[idx].species_id) else { return };
+        let pos_index = pos.to_index(self.world.cols);
+        for sensor in &spec.sensors {
+            let value = sensor.evaluate(&self.fields, pos_index, |name| self.own_contribution(idx, pos, name));
+            self.world.agents[idx].set_var(sensor.target_var, value);
+        }
+    }
+
+    /// Phase 3 : l'agent absorbe les champs de ses voisins d'une autre espèce.
+    ///
+    /// Pour chaque champ `f` qu'un voisin émet, que l'agent perçoit par un
+    /// capteur et pour lequel il a une variable `f`, toute la valeur émise
+    /// passe de la variable `f` du voisin à celle de l'agent (le mouton
+    /// affamé mange l'herbe). Retourne les voisins modifiés.
+    fn absorb(&mut self, idx: usize) -> Vec<usize> {
+        let mut preys: Vec<usize> = Vec::new();
+        for (prey, prey_var, own_var) in self.absorption_claims(idx) {
+            let amount = self.world.agents[prey].get_var(prey_var);
+            if amount <= 0 { continue; }
+            self.world.agents[prey].inc_var(prey_var, -amount);
+            self.world.agents[idx].inc_var(own_var, amount);
+            if !preys.contains(&prey) {
+                preys.push(prey);
+            }
+        }
+        preys
+    }
+
+    /// Phase 3 en mode `Sync` : tous les prélèvements sont calculés sur les
+    /// variables du début de la phase, puis appliqués. Une valeur convoitée
+    /// par plusieurs agents est partagée à parts égales, le reste de la
+    /// division allant à l'un d'eux tiré au hasard.
+    fn absorb_all(&mut self, alive: &[usize]) {
+        let mut order = Vec::new();
+        let mut claimants: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+        for &idx in alive {
+            for (prey, prey_var, own_var) in self.absorption_claims(idx) {
+                claimants.entry((prey, prey_var)).or_insert_with(|| {
+                    order.push((prey, prey_var));
+                    Vec::new()
+                }).push((idx, own_var));
+            }
+        }
+
+        let amounts: Vec<i32> = order.iter()
+            .map(|&(prey, prey_var)| self.world.agents[prey].get_var(prey_var))
+            .collect();
+        for (key, amount) in order.into_iter().zip(amounts) {
+            if amount <= 0 { continue; }
+            let takers = &claimants[&key];
+            let n = takers.len() as i32;
+            let lucky = self.rng.random_range(0..takers.len());
+            self.world.agents[key.0].inc_var(key.1, -amount);
+            for (i, &(idx, own_var)) in takers.iter().enumerate() {
+                let share = amount / n + if i == lucky { amount % n } else { 0 };
+                self.world.agents[idx].inc_var(own_var, share);
+            }
+        }
+    }
+
+    /// Prélèvements que l'agent peut faire sur ses voisins :
+    /// (voisin, variable du voisin, variable de l'agent)
+    fn absorption_claims(&self, idx: usize) -> Vec<(usize, usize, usize)> {
+        let mut claims = Vec::new();
+        let agent = &self.world.agents[idx];
+        if !agent.alive { return claims; }
+        let Some(spec) = self.species_defs.get(agent.species_id) else { return claims };
+
+        for pos in agent.pos.neighbors8(self.world.rows, self.world.cols) {
+            let Some(other) = self.world.get(pos).and_then(|c| c.agent) else { continue };
+            let prey = &self.world.agents[other];
+            if !prey.alive || prey.species_id == agent.species_id { continue; }
+            let Some(prey_spec) = self.species_defs.get(prey.species_id) else { continue };
+
+            for fdef in &prey_spec.field_defs {
+                let senses = spec.sensors.iter()
+                    .any(|s| s.terms.iter().any(|t| t.field_name == fdef.name));
+                let (Some(own_var), Some(prey_var)) = (spec.var_index(&fdef.name), prey_spec.var_index(&fdef.name))
+                    else { continue };
+                if senses {
+                    claims.push((other, prey_var, own_var));
+                }
+            }
+        }
+        claims
+    }
+
+    /// Phase 4 : ajoute le TimeStepValue de chaque variable.
+    ///
+    /// Le pas s'applique après les capteurs et l'absorption : une variable
+    /// captée vaut la lecture du champ plus son pas. Les règles de statut et
+    /// de naissance lisent ensuite les valeurs incrémentées.
+    fn apply_timesteps(&mut self, idx: usize) {
+        let agent = &mut self.world.agents[idx];
+        let Some(spec) = self.species_defs.get(agent.species_id) else { return };
+        for (var_idx, var) in spec.vars.iter().enumerate() {
+            if var.timestep != 0 {
+                agent.inc_var(var_idx, var.timestep);
+            }
+        }
+    }
+
+    /// Phase 5 : règles de statut. `void` retire l'agent de la grille, `end`
+    /// arrête la simulation à la fin du step et `trace` marque la case.
+    fn apply_status_rules(&mut self, idx: usize) {
+        let agent = &self.world.agents[idx];
+        let Some(spec) = self.species_defs.get(agent.species_id) else { return };
+        let Some(rule) = rules::select_status_rule(agent, &spec.status_rules) else { return };
+        let target = self.species_defs.iter().position(|s| s.name == rule.new_status);
+        let pos = agent.pos;
+
+        match StatusChange::from_status(&rule.new_status, target.is_some()) {
+            StatusChange::Void => self.world.kill_agent(idx),
+            StatusChange::End => self.running = false,
+            StatusChange::Trace => self.world.set_trace(pos),
+            StatusChange::Become(_) => {
+                if let Some(target) = target {
+                    self.metamorphose(idx, target);
+                }
+            }
         }
+    }
 
-        // 2) diffusion des champs (simplifié pour éviter les erreurs de borrow)
-        // Nous devons séparer cette logique pour éviter le mutable/immutable borrow conflict
+    /// Fait passer un agent dans une autre espèce sans changer son identité
+    /// ni sa position. Les variables sont réinitialisées selon l'espèce cible,
+    /// y compris quand l'agent reste dans la même espèce ; les variables
+    /// héritées reprennent les valeurs de l'espèce quittée.
+    fn metamorphose(&mut self, idx: usize, target: usize) {
+        let spec = &self.species_defs[target];
+        let agent = &mut self.world.agents[idx];
+        let previous = &self.species_defs[agent.species_id];
 
-        // 3) mise à jour des variables (TimeStepValue)
-        for _agent in self.world.agents.iter_mut().filter(|a| a.alive) {
-            // Pour simplifier, on ne fait rien pour l'instant
-            // agent.inc_var(0, 1); 
+        agent.vars = spec.initial_vars(Some((previous, &agent.vars)));
+        agent.species_id = target;
+        agent.species = spec.kind;
+        agent.color = spec.color;
+        agent.status = spec.name.clone();
+    }
+
+    /// Phase 6 : chaque animal vise la case voisine libre la plus favorable,
+    /// puis les conflits sont arbitrés selon `move_policy`
+    fn move_animals(&mut self) {
+        let mut intents = Vec::new();
+        for idx in 0..self.world.agents.len() {
+            let agent = &self.world.agents[idx];
+            if !agent.alive || agent.species != SpeciesKind::Animal { continue; }
+            if let Some((target, value)) = self.best_free_neighbour(idx, true) {
+                intents.push(MoveIntent { agent: idx, target, value });
+            }
         }
 
-        // 4) capteurs (simplifié)
-        
-        // 5) appliquer règles (simplifié pour éviter les problèmes de borrow)
-        println!("Step exécuté - {} agents vivants", 
-                 self.world.agents.iter().filter(|a| a.alive).count());
+        // les cases visées étaient libres au début de la phase et sont
+        // toutes distinctes après arbitrage : la grille reste cohérente
+        for intent in movement::resolve_moves(intents, self.move_policy, &mut self.rng) {
+            self.world.move_agent(intent.agent, intent.target);
+        }
     }
+
+    /// Choisit une case voisine libre pour un déplacement ou une naissance.
+    ///
+    /// La valeur d'une case est la somme des capteurs de l'espèce évalués sur
+    /// cette case, sans la propre émission de l'agent. Avec `may_stay`,
+    /// l'agent ne quitte pas sa case si elle est strictement meilleure. Sans
+    /// capteur, la case est tirée au hasard. Les égalités sont départagées
+    /// par le générateur de l'engine. Retourne la case choisie et sa valeur.
+    fn best_free_neighbour(&mut self, idx: usize, may_stay: bool) -> Option<(Position, i32)> {
+        let agent = &self.world.agents[idx];
+        let spec = self.species_defs.get(agent.species_id)?;
+        let free: Vec<Position> = agent.pos
+            .neighbors8(self.world.rows, self.world.cols)
+            .into_iter()
+            .filter(|p| self.world.is_position_free(p))
+            .collect();
+
+        if spec.sensors.is_empty() {
+            return free.choose(&mut self.rng).map(|&p| (p, 0));
+        }
+
+        let cols = self.world.cols;
+        let value = |pos: Position| -> i32 {
+            spec.sensors.iter()
+                .map(|s| s.evaluate(&self.fields, pos.to_index(cols), |name| self.own_contribution(idx, pos, name)))
+                .sum()
+        };
+
+        let best = free.iter().map(|&p| value(p)).max()?;
+        if may_stay && value(agent.pos) > best {
+            return None;
+        }
+        let candidates: Vec<Position> = free.into_iter().filter(|&p| value(p) == best).collect();
+        candidates.choose(&mut self.rng).map(|&p| (p, best))
+    }
+
+    /// Phase 7a : décide la naissance d'un agent, sur ses variables d'avant
+    /// la règle de statut. Retourne le parent et l'espèce de l'enfant.
+    fn select_birth(&self, idx: usize) -> Option<(usize, usize)> {
+        let parent = &self.world.agents[idx];
+        let spec = self.species_defs.get(parent.species_id)?;
+        let rule = rules::select_birth_rule(parent, &spec.birth_rules)?;
+        let child = self.species_defs.iter().position(|s| s.name == rule.child_status)?;
+        Some((idx, child))
+    }
+
+    /// Phase 7b : place les enfants une fois tous les agents traités, pour
+    /// qu'ils n'agissent pas pendant le step de leur naissance. Chaque enfant
+    /// prend la meilleure case libre autour de son parent, s'il en reste une ;
+    /// un parent disparu entre-temps n'a pas d'enfant.
+    fn place_births(&mut self, mut births: Vec<(usize, usize)>) {
+        births.shuffle(&mut self.rng);
+        for (parent_idx, child_id) in births {
+            if !self.world.agents[parent_idx].alive { continue; }
+            let Some((pos, _)) = self.best_free_neighbour(parent_idx, false) else { continue };
+
+            let parent = &self.world.agents[parent_idx];
+            let vars = self.species_defs[child_id]
+                .initial_vars(Some((&self.species_defs[parent.species_id], &parent.vars)));
+            let child = &self.species_defs[child_id];
+            let id = self.world.spawn_agent(pos, child.kind, child_id, child.name.clone(), child.color, vars.len());
+            self.world.agents[id].vars = vars;
+        }
+    }
+}
+
+/// Champs dont la valeur est lue pendant la simulation : ceux qu'un capteur
+/// perçoit. L'absorption ne porte que sur des champs perçus par l'absorbeur
+/// et lit les variables des agents, pas les champs calculés ; elle n'ajoute
+/// donc rien. Les autres champs ne sont ni alloués ni calculés.
+fn used_fields(species_defs: &[SpeciesDef]) -> HashSet<&str> {
+    species_defs.iter()
+        .flat_map(|spec| &spec.sensors)
+        .flat_map(|sensor| &sensor.terms)
+        .map(|term| term.field_name.as_str())
+        .collect()
 }
@@ -1,10 +1,60 @@
+use crate::dsl::ast::{Decay, FieldKind, Metric};
 use crate::model::position::Position;
+use std::collections::VecDeque;
 
 /// Définition d’un champ (issu du DSL `field`)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct FieldDef {
     pub name: String,
-    pub step: i32, // DistanceStepValue
+    /// Décroissance par pas de distance (opposé du pas du DSL), positive ;
+    /// 0 pour un champ uniforme sur toute la grille
+    pub step: i32,
+    pub metric: Metric,
+    pub decay: Decay,
+    pub kind: FieldKind,
+}
+
+impl FieldDef {
+    /// Valeur apportée à distance `dist` par une source de valeur
+    /// `var_value`, arrondie et jamais négative.
+    ///
+    /// Tous les profils valent `var_value` sur la source et dépendent de
+    /// `x = dist * step / var_value`, qui atteint 1 là où le profil
+    /// linéaire s'annule.
+    pub fn value_at(&self, var_value: i32, dist: f32) -> i32 {
+        if var_value <= 0 {
+            return 0;
+        }
+        if self.step == 0 {
+            return var_value;
+        }
+        let v = var_value as f32;
+        let x = dist * self.step as f32 / v;
+        let val = match self.decay {
+            Decay::Linear => v * (1.0 - x),
+            Decay::Exponential => v * (-x).exp(),
+            Decay::Gaussian => v * (-x * x).exp(),
+            Decay::InverseSquare => v / ((1.0 + x) * (1.0 + x)),
+        };
+        (val.round() as i32).max(0)
+    }
+
+    /// Distance entre deux cases selon la métrique du champ
+    pub fn distance(&self, a: Position, b: Position) -> f32 {
+        let dr = a.row.abs_diff(b.row) as f32;
+        let dc = a.col.abs_diff(b.col) as f32;
+        match self.metric {
+            Metric::Chebyshev => dr.max(dc),
+            Metric::Manhattan => dr + dc,
+            Metric::Euclidean => (dr * dr + dc * dc).sqrt(),
+        }
+    }
+
+    /// Plus petite distance entière à laquelle la source ne contribue plus,
+    /// bornée par `max`
+    fn reach(&self, var_value: i32, max: i32) -> i32 {
+        (1..max).find(|&d| self.value_at(var_value, d as f32) == 0).unwrap_or(max)
+    }
 }
 
 /// Buffer de valeurs pour un champ donné (taille = grille entière)
@@ -14,15 +64,27 @@ pub struct Field {
     pub values: Vec<i32>, // taille = rows * cols
     pub rows: usize,
     pub cols: usize,
+    /// Cases qui arrêtent la propagation (DSL `block`), `None` si aucune
+    obstacles: Option<Vec<bool>>,
+    blocked: usize, // nombre de cases bloquées
+    /// Mémoire d'un champ persistant, `None` pour un champ instantané
+    trail: Option<Vec<f32>>,
 }
 
 impl Field {
     pub fn new(def: FieldDef, rows: usize, cols: usize) -> Self {
+        let trail = match def.kind {
+            FieldKind::Instant => None,
+            FieldKind::Persistent { .. } => Some(vec![0.0; rows * cols]),
+        };
         Self {
             def,
             values: vec![0; rows * cols],
             rows,
             cols,
+            obstacles: None,
+            blocked: 0,
+            trail,
         }
     }
 
@@ -31,18 +93,124 @@ impl Field {
         pos.to_index(self.cols)
     }
 
-    /// Remet toutes les cases à zéro
+    /// Remet toutes les cases à zéro, mémoire comprise
     pub fn clear(&mut self) {
         self.values.fill(0);
+        if let Some(trail) = self.trail.as_mut() {
+            trail.fill(0.0);
+        }
+    }
+
+    /// Prépare le champ pour un nouveau pas de simulation, avant que les
+    /// agents n'y posent leurs sources.
+    ///
+    /// Un champ instantané est remis à zéro. Un champ persistant ajoute à sa
+    /// mémoire les sources posées au pas précédent, la diffuse vers les cases
+    /// voisines puis l'évapore ; ses valeurs repartent de cette mémoire.
+    pub fn tick(&mut self) {
+        let FieldKind::Persistent { diffusion, evaporation } = self.def.kind else {
+            self.clear();
+            return;
+        };
+        let Some(mut trail) = self.trail.take() else { return };
+
+        // les valeurs valent la mémoire arrondie plus les sources du pas
+        for (m, &v) in trail.iter_mut().zip(&self.values) {
+            *m += (v - m.round() as i32) as f32;
+        }
+
+        // chaque case cède `diffusion` à parts égales entre ses voisines
+        // libres : la quantité totale est conservée, sauf sur un obstacle
+        // qui perd sa mémoire
+        let mut next = vec![0.0; trail.len()];
+        for (i, &m) in trail.iter().enumerate() {
+            if m == 0.0 {
+                continue;
+            }
+            let pos = Position::new(i / self.cols, i % self.cols);
+            if self.is_blocked(pos) {
+                continue;
+            }
+            let open: Vec<Position> = pos.neighbors8(self.rows, self.cols)
+                .into_iter()
+                .filter(|&n| !self.is_blocked(n))
+                .collect();
+            if open.is_empty() {
+                next[i] += m;
+                continue;
+            }
+            next[i] += m * (1.0 - diffusion);
+            let share = m * diffusion / open.len() as f32;
+            for n in open {
+                next[n.to_index(self.cols)] += share;
+            }
+        }
+
+        for (m, v) in next.iter_mut().zip(self.values.iter_mut()) {
+            *m *= 1.0 - evaporation;
+            *v = m.round() as i32;
+        }
+        self.trail = Some(next);
+    }
+
+    /// Retire tous les obstacles
+    pub fn clear_obstacles(&mut self) {
+        self.obstacles = None;
+        self.blocked = 0;
+    }
+
+    /// Marque une case comme obstacle : le champ n'y entre pas
+    pub fn block(&mut self, pos: Position) {
+        let i = self.idx(pos);
+        let len = self.values.len();
+        let cell = &mut self.obstacles.get_or_insert_with(|| vec![false; len])[i];
+        if !*cell {
+            *cell = true;
+            self.blocked += 1;
+        }
+    }
+
+    /// Vrai si les obstacles sont exactement ces cases (sans doublon)
+    pub fn obstacles_match(&self, cells: &[Position]) -> bool {
+        cells.len() == self.blocked && cells.iter().all(|&p| self.is_blocked(p))
+    }
+
+    /// Vrai si le champ garde la mémoire des pas précédents
+    pub fn is_persistent(&self) -> bool {
+        self.trail.is_some()
     }
 
-    /// Ajoute une contribution depuis une position
+    /// Vrai si la case arrête la propagation du champ
+    pub fn is_blocked(&self, pos: Position) -> bool {
+        self.obstacles.as_ref().is_some_and(|o| o[self.idx(pos)])
+    }
+
+    /// Ajoute une contribution depuis une position. La valeur décroît avec
+    /// la distance selon la métrique et le profil du champ.
     pub fn add_source(&mut self, pos: Position, var_value: i32) {
+        self.stamp(pos, var_value, 1);
+    }
+
+    /// Retire une contribution ajoutée par `add_source` avec les mêmes
+    /// arguments (les obstacles ne doivent pas avoir changé entre-temps)
+    pub fn remove_source(&mut self, pos: Position, var_value: i32) {
+        self.stamp(pos, var_value, -1);
+    }
+
+    /// Ajoute (`sign = 1`) ou retire (`sign = -1`) la contribution d'une source
+    fn stamp(&mut self, pos: Position, var_value: i32, sign: i32) {
         if var_value <= 0 {
             return;
         }
-        let step = self.def.step;
-        let delta = ((var_value as f32) / (step as f32)).ceil() as i32;
+        // toutes les métriques majorent Chebyshev : le carré de côté
+        // 2 * delta + 1 contient toutes les cases atteintes
+        let delta = self.def.reach(var_value, self.rows.max(self.cols) as i32);
+
+        if self.obstacles.is_some() {
+            self.stamp_blocked(pos, var_value, delta, sign);
+            return;
+        }
+
         let r0 = pos.row as i32;
         let c0 = pos.col as i32;
 
@@ -53,14 +221,98 @@ impl Field {
                 if rr < 0 || cc < 0 || rr >= self.rows as i32 || cc >= self.cols as i32 {
                     continue;
                 }
-                let dist = dr.abs().max(dc.abs());
-                let val = var_value - dist * step;
+                let at = Position::new(rr as usize, cc as usize);
+                let val = self.def.value_at(var_value, self.def.distance(pos, at));
                 if val > 0 {
-                    let i = (rr as usize) * self.cols + (cc as usize);
-                    self.values[i] += val;
+                    self.values[at.to_index(self.cols)] += sign * val;
+                }
+            }
+        }
+    }
+
+    /// Propagation en largeur autour des obstacles, dans le voisinage de
+    /// von Neumann pour Manhattan et de Moore sinon : la distance est celle
+    /// du plus court chemin qui les contourne. En euclidien, c'est le plus
+    /// grand du chemin de Moore et de la distance à vol d'oiseau.
+    fn stamp_blocked(&mut self, pos: Position, var_value: i32, delta: i32, sign: i32) {
+        for (p, path) in self.paths_around_obstacles(pos, delta, None) {
+            let val = self.def.value_at(var_value, self.blocked_distance(pos, p, path));
+            if val > 0 {
+                self.values[p.to_index(self.cols)] += sign * val;
+            }
+        }
+    }
+
+    /// Cases atteintes depuis `source` en au plus `delta` pas sans traverser
+    /// d'obstacle, avec la longueur du plus court chemin. S'arrête dès que
+    /// `target` est atteinte.
+    fn paths_around_obstacles(&self, source: Position, delta: i32, target: Option<Position>) -> Vec<(Position, i32)> {
+        let obstacles = self.obstacles.as_ref().expect("obstacles required");
+
+        // fenêtre locale (2*delta+1)² centrée sur la source
+        let side = 2 * delta as usize + 1;
+        let origin_r = source.row as i32 - delta;
+        let origin_c = source.col as i32 - delta;
+        let mut visited = vec![false; side * side];
+        let local = |p: Position| {
+            (p.row as i32 - origin_r) as usize * side + (p.col as i32 - origin_c) as usize
+        };
+
+        let mut reached = Vec::new();
+        let mut queue = VecDeque::new();
+        visited[local(source)] = true;
+        queue.push_back((source, 0));
+
+        while let Some((p, path)) = queue.pop_front() {
+            reached.push((p, path));
+            if Some(p) == target {
+                break;
+            }
+            if path == delta {
+                continue;
+            }
+            let neighbors = match self.def.metric {
+                Metric::Manhattan => p.neighbors4(self.rows, self.cols),
+                Metric::Chebyshev | Metric::Euclidean => p.neighbors8(self.rows, self.cols),
+            };
+            for n in neighbors {
+                let li = local(n);
+                if visited[li] || obstacles[n.to_index(self.cols)] {
+                    continue;
                 }
+                visited[li] = true;
+                queue.push_back((n, path + 1));
             }
         }
+        reached
+    }
+
+    /// Distance utilisée autour des obstacles pour un chemin de `path` pas
+    fn blocked_distance(&self, source: Position, at: Position, path: i32) -> f32 {
+        match self.def.metric {
+            Metric::Euclidean => self.def.distance(source, at).max(path as f32),
+            Metric::Chebyshev | Metric::Manhattan => path as f32,
+        }
+    }
+
+    /// Part de la valeur en `at` apportée par une source posée en `source`,
+    /// égale à ce que `add_source` y a ajouté. Autour d'obstacles, elle suit
+    /// le même parcours que `add_source`, arrêté dès que `at` est atteinte.
+    pub fn contribution(&self, source: Position, var_value: i32, at: Position) -> i32 {
+        if self.obstacles.is_none() {
+            return self.def.value_at(var_value, self.def.distance(source, at));
+        }
+        if var_value <= 0 || (self.is_blocked(at) && at != source) {
+            return 0;
+        }
+        let delta = self.def.reach(var_value, self.rows.max(self.cols) as i32);
+        if self.def.distance(source, at) > delta as f32 {
+            return 0;
+        }
+        match self.paths_around_obstacles(source, delta, Some(at)).last() {
+            Some(&(p, path)) if p == at => self.def.value_at(var_value, self.blocked_distance(source, at, path)),
+            _ => 0,
+        }
     }
 
     /// Valeur du champ à une position
@@ -1,5 +1,6 @@
 pub mod engine;
 pub mod field;
+pub mod movement;
 pub mod rules;
 pub mod sensor;
 
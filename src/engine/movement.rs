@@ -0,0 +1,65 @@
+use crate::model::position::Position;
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use std::collections::HashMap;
+
+/// Politique d'arbitrage quand plusieurs animaux visent la même case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MovePolicy {
+    /// Premier arrivé dans un ordre tiré au hasard à chaque step
+    #[default]
+    FirstCome,
+    /// La plus forte valeur de capteurs l'emporte (égalités au hasard)
+    HighestValue,
+    /// Tirage uniforme parmi les prétendants
+    Lottery,
+}
+
+impl MovePolicy {
+    /// Nom utilisé en ligne de commande
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "first-come" => Some(Self::FirstCome),
+            "highest-value" => Some(Self::HighestValue),
+            "lottery" => Some(Self::Lottery),
+            _ => None,
+        }
+    }
+}
+
+/// Intention de déplacement d'un agent, calculée sur l'état du début de phase
+#[derive(Debug, Clone, Copy)]
+pub struct MoveIntent {
+    pub agent: usize,
+    pub target: Position,
+    pub value: i32, // valeur des capteurs sur la case visée
+}
+
+/// Départage les intentions : au plus un agent par case visée.
+/// Retourne les déplacements retenus, les perdants restent sur place.
+pub fn resolve_moves(mut intents: Vec<MoveIntent>, policy: MovePolicy, rng: &mut StdRng) -> Vec<MoveIntent> {
+    // le mélange fixe l'ordre d'arrivée : aucun agent n'est favorisé par son index
+    intents.shuffle(rng);
+
+    let mut order = Vec::new();
+    let mut contenders: HashMap<Position, Vec<MoveIntent>> = HashMap::new();
+    for intent in intents {
+        contenders.entry(intent.target).or_insert_with(|| {
+            order.push(intent.target);
+            Vec::new()
+        }).push(intent);
+    }
+
+    order.into_iter().map(|target| {
+        let group = &contenders[&target];
+        match policy {
+            MovePolicy::FirstCome => group[0],
+            MovePolicy::HighestValue => {
+                let best = group.iter().map(|i| i.value).max().expect("groupe non vide");
+                let tied: Vec<&MoveIntent> = group.iter().filter(|i| i.value == best).collect();
+                **tied.choose(rng).expect("groupe non vide")
+            }
+            MovePolicy::Lottery => *group.choose(rng).expect("groupe non vide"),
+        }
+    }).collect()
+}
@@ -1,78 +1,76 @@
-use crate::model::world::World;
 use crate::model::agent::Agent;
-use crate::dsl::ast::{StatusRule as AstStatusRule, BirthRule as AstBirthRule};
+use crate::dsl::ast::CompareOp;
 
-/// Alias pour les règles du DSL
-pub type StatusRule = AstStatusRule;
-pub type BirthRule = AstBirthRule;
-
-/// Applique les règles de statut à un agent donné
-pub fn apply_status_rules(agent: &mut Agent, rules: &[StatusRule], _world: &World) -> bool {
-    for rule in rules {
-        // Évaluation simplifiée basée sur la structure du DSL
-        let should_apply = if let (Some(_var_name), Some(threshold)) = (&rule.variable, rule.threshold) {
-            // Pour simplifier, on suppose que la variable 0 correspond à la première variable
-            let agent_val = agent.get_var(0);
-            if let Some(less_than) = rule.less_than {
-                if less_than {
-                    agent_val < threshold
-                } else {
-                    agent_val > threshold
-                }
-            } else {
-                agent_val == threshold
-            }
-        } else {
-            true // Règle sans condition = toujours vraie
-        };
+/// Condition compilée `var op threshold`, la variable est résolue en index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    pub var: usize,
+    pub op: CompareOp,
+    pub threshold: i32,
+}
 
-        if should_apply {
-            // Appliquer le nouveau statut (simplifié)
-            if rule.new_status == "dead" {
-                agent.alive = false;
-            }
-            return true; // Une seule règle par step
-        }
+impl Condition {
+    pub fn eval(&self, agent: &Agent) -> bool {
+        self.op.eval(agent.get_var(self.var), self.threshold)
     }
-    false
 }
 
-/// Applique les règles de naissance
-pub fn apply_birth_rules(parent: &Agent, rules: &[BirthRule], world: &mut World) {
-    for rule in rules {
-        // Évaluation simplifiée
-        let should_apply = if let (Some(_var_name), Some(threshold)) = (&rule.variable, rule.threshold) {
-            let parent_val = parent.get_var(0);
-            if let Some(less_than) = rule.less_than {
-                if less_than {
-                    parent_val < threshold
-                } else {
-                    parent_val > threshold
-                }
-            } else {
-                parent_val == threshold
-            }
-        } else {
-            true // Règle sans condition
-        };
+/// Règle de statut compilée (DSL `status [cond] new_status`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusRule {
+    pub condition: Option<Condition>, // `None` : toujours vraie
+    pub new_status: String,
+}
+
+/// Règle de naissance compilée (DSL `birth [cond] child`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BirthRule {
+    pub condition: Option<Condition>, // `None` : toujours vraie
+    pub child_status: String,
+}
+
+/// Statuts prédéfinis, utilisables sans être déclarés comme espèces
+pub const BUILTIN_STATUSES: [&str; 3] = ["void", "end", "trace"];
 
-        if should_apply {
-            // Créer un nouvel agent adjacent (position simplifiée)
-            let mut new_pos = parent.pos.clone();
-            new_pos.col = (new_pos.col + 1).min(world.cols - 1);
-            
-            // Vérifier si la position est libre 
-            if world.is_position_free(&new_pos) {
-                let _new_agent_id = world.spawn_agent(
-                    new_pos,
-                    parent.species,
-                    parent.species_id,
-                    "alive".to_string(),
-                    parent.color,
-                    parent.vars.len(),
-                );
-            }
-            break; // Une seule règle de naissance par step
+/// Effet d'une règle de statut déclenchée
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusChange {
+    /// `void` : l'agent disparaît de la grille
+    Void,
+    /// `end` : arrêt de la simulation
+    End,
+    /// `trace` : l'agent marque sa case et garde son statut
+    Trace,
+    /// passage dans le statut déclaré de ce nom
+    Become(String),
+}
+
+impl StatusChange {
+    /// Effet d'un nom de statut ; une espèce déclarée sous un nom prédéfini
+    /// (`mineral void 000`) reste un statut ordinaire
+    pub fn from_status(name: &str, declared: bool) -> Self {
+        match name {
+            _ if declared => StatusChange::Become(name.to_string()),
+            "void" => StatusChange::Void,
+            "end" => StatusChange::End,
+            "trace" => StatusChange::Trace,
+            _ => StatusChange::Become(name.to_string()),
         }
     }
 }
+
+/// Retourne la première règle de statut déclenchée par l'agent :
+/// une seule règle s'applique par step
+pub fn select_status_rule<'a>(agent: &Agent, rules: &'a [StatusRule]) -> Option<&'a StatusRule> {
+    rules.iter().find(|rule| fires(agent, &rule.condition))
+}
+
+/// Retourne la première règle de naissance déclenchée par le parent :
+/// au plus une naissance par agent et par step
+pub fn select_birth_rule<'a>(parent: &Agent, rules: &'a [BirthRule]) -> Option<&'a BirthRule> {
+    rules.iter().find(|rule| fires(parent, &rule.condition))
+}
+
+fn fires(agent: &Agent, condition: &Option<Condition>) -> bool {
+    condition.as_ref().is_none_or(|c| c.eval(agent))
+}
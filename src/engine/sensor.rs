@@ -18,27 +18,19 @@ impl SensorDef {
     /// Évalue le capteur en un point donné
     /// - `fields`: liste des champs par nom
     /// - `pos`: index 1D dans la grille
-    /// - `self_contrib`: contributions à retirer pour éviter que l’agent lise son propre champ
+    /// - `self_contrib`: contribution de l’agent à un champ, à retirer pour éviter qu’il lise son propre champ
     pub fn evaluate(
         &self,
         fields: &std::collections::HashMap<String, Field>,
         pos_index: usize,
-        self_contrib: &[(String, i32)],
+        self_contrib: impl Fn(&str) -> i32,
     ) -> i32 {
         let mut acc = 0.0;
 
         for term in &self.terms {
             if let Some(field) = fields.get(&term.field_name) {
-                let mut value = field.values[pos_index];
-
-                // retirer l’auto-contribution si nécessaire
-                if let Some(&(ref fname, sub)) =
-                    self_contrib.iter().find(|(fname, _)| fname == &term.field_name)
-                {
-                    if fname == &term.field_name {
-                        value -= sub;
-                    }
-                }
+                // retirer l’auto-contribution
+                let value = field.values[pos_index] - self_contrib(&term.field_name);
 
                 acc += (value as f32) * term.weight;
             }
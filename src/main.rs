@@ -4,11 +4,57 @@ mod engine;
 mod utils;
 
 use std::fs;
+use clap::Parser;
+use crate::dsl::ast::UpdateMode;
+use crate::dsl::compile::compile;
 use crate::dsl::parser::parse_file;
 use crate::engine::engine::Engine;
-use crate::model::world::World;
+use crate::engine::movement::MovePolicy;
+use crate::model::builder::WorldBuilder;
+use crate::utils::log::{self, LogLevel, Logger};
 
-fn run_level(filename: &str) -> anyhow::Result<()> {
+/// Simulateur multi-agents OSCAR
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Cli {
+    /// Fichier DSL à simuler (par défaut : tous les mondes fournis)
+    #[arg(short, long)]
+    config: Option<String>,
+
+    /// Graine aléatoire, prioritaire sur la directive `seed` du DSL
+    #[arg(short, long)]
+    seed: Option<u64>,
+
+    /// Arbitrage des déplacements concurrents : first-come, highest-value ou lottery
+    #[arg(long, default_value = "first-come", value_parser = parse_move_policy)]
+    moves: MovePolicy,
+
+    /// Mode de mise à jour : sync ou async, prioritaire sur la directive `mode` du DSL
+    #[arg(long, value_parser = parse_update_mode)]
+    mode: Option<UpdateMode>,
+
+    /// Nombre de steps à exécuter
+    #[arg(long, default_value_t = 10)]
+    steps: usize,
+
+    /// Niveau de log minimal : debug, info, warning ou error
+    #[arg(long, default_value = "info", value_parser = parse_log_level)]
+    log_level: LogLevel,
+}
+
+fn parse_move_policy(name: &str) -> Result<MovePolicy, String> {
+    MovePolicy::from_name(name).ok_or_else(|| format!("politique de déplacement inconnue: {name}"))
+}
+
+fn parse_log_level(name: &str) -> Result<LogLevel, String> {
+    LogLevel::from_name(name).ok_or_else(|| format!("niveau de log inconnu: {name}"))
+}
+
+fn parse_update_mode(name: &str) -> Result<UpdateMode, String> {
+    UpdateMode::from_name(name).ok_or_else(|| format!("mode de mise à jour inconnu: {name}"))
+}
+
+fn run_level(filename: &str, cli: &Cli) -> anyhow::Result<()> {
     println!("Chargement du niveau {filename}");
 
     // 1) Lire le fichier DSL
@@ -16,33 +62,43 @@ fn run_level(filename: &str) -> anyhow::Result<()> {
     let config_ast = parse_file(&txt)?;
     println!("Config AST = {:#?}", config_ast);
 
-    // 2) Créer le World
-    let world = World::new(
-        config_ast.world.as_ref().unwrap().rows,
-        config_ast.world.as_ref().unwrap().cols,
-    );
+    // 2) Compiler le AST en définitions d’espèces
+    let species_defs = compile(&config_ast)?;
 
-    // 3) Compiler le AST en définitions d’espèces + agents
-    // (ici on laisse un placeholder, à implémenter dans dsl::compile)
-    let species_defs = vec![];
+    // 3) Créer le World et placer les agents
+    // sans graine imposée, on en tire une et on l'affiche pour pouvoir rejouer
+    let seed = cli.seed.or(config_ast.seed).unwrap_or_else(rand::random);
+    println!("Graine {seed}");
+    let mut builder = WorldBuilder::from_config(&config_ast)?.with_seed(seed);
+    builder.place_agents(&config_ast)?;
+    let (mut world, agents, rng) = builder.build_with_rng();
+    world.agents = agents;
 
-    // 4) Lancer l’engine
-    let mut engine = Engine::new(world, species_defs);
+    // 4) Lancer l’engine avec le même générateur que le placement
+    let mut engine = Engine::new(world, species_defs)
+        .with_rng(rng)
+        .with_move_policy(cli.moves)
+        .with_update_mode(cli.mode.or(config_ast.mode).unwrap_or_default());
 
     // 5) Boucle principale
-    for step in 0..10 {
+    for step in 0..cli.steps {
         if !engine.running {
             println!("Simulation arrêtée au step {step}");
             break;
         }
         println!("--- Step {step} ---");
         engine.step();
+        println!("{} agents vivants",
+                 engine.world.agents.iter().filter(|a| a.alive).count());
     }
 
     Ok(())
 }
 
 fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    log::init_logger(Logger::new().with_level(cli.log_level));
+
     println!(
         r#"
 ###################################################################
@@ -70,8 +126,13 @@ fn main() -> anyhow::Result<()> {
         "worlds/oscar7.txt",
     ];
 
+    let levels: Vec<&str> = match &cli.config {
+        Some(file) => vec![file.as_str()],
+        None => levels.to_vec(),
+    };
+
     for file in levels {
-        if let Err(e) = run_level(file) {
+        if let Err(e) = run_level(file, &cli) {
             eprintln!("Erreur dans {file}: {e:?}");
         }
     }
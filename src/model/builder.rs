@@ -3,6 +3,9 @@ use crate::model::agent::{Agent, AgentId, SpeciesKind};
 use crate::model::position::Position;
 use crate::model::world::World;
 use anyhow::{Result, anyhow};
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::SeedableRng;
 use std::collections::HashMap;
 
 /// Structure pour construire le monde à partir de la configuration DSL
@@ -11,14 +14,17 @@ pub struct WorldBuilder {
     pub agents: Vec<Agent>,
     pub species_map: HashMap<String, SpeciesInfo>,
     next_agent_id: AgentId,
+    rng: StdRng, // tirages des patterns `(void,tree,...)`
 }
 
 /// Information sur une espèce définie dans le DSL
 #[derive(Debug, Clone)]
 pub struct SpeciesInfo {
+    pub species_id: usize,
     pub kind: SpeciesKind,
     pub color: u32,
     pub var_names: Vec<String>,
+    pub init_values: Vec<i32>, // valeurs initiales (une valeur héritée vaut 0 au placement)
     pub default_status: String,
 }
 
@@ -34,7 +40,7 @@ impl WorldBuilder {
 
         // Créer la carte des espèces
         let mut species_map = HashMap::new();
-        for species in &config.species {
+        for (species_id, species) in config.species.iter().enumerate() {
             let kind = match species.kind {
                 crate::dsl::ast::SpeciesKind::Mineral => SpeciesKind::Mineral,
                 crate::dsl::ast::SpeciesKind::Vegetal => SpeciesKind::Vegetal,
@@ -43,12 +49,18 @@ impl WorldBuilder {
 
             let color = parse_color(&species.color)?;
             let var_names = species.vars.iter().map(|v| v.name.clone()).collect();
+            let init_values = species.vars.iter().map(|v| match v.init_value {
+                Value::Int(n) => n,
+                Value::Str(_) => 0,
+            }).collect();
             let default_status = species.name.clone(); // Par défaut, le statut est le nom de l'espèce
 
             species_map.insert(species.name.clone(), SpeciesInfo {
+                species_id,
                 kind,
                 color,
                 var_names,
+                init_values,
                 default_status,
             });
         }
@@ -58,10 +70,21 @@ impl WorldBuilder {
             agents: Vec::new(),
             species_map,
             next_agent_id: 0,
+            rng: match config.seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_os_rng(),
+            },
         })
     }
 
-    /// Ajoute un agent à une position donnée
+    /// Fixe la graine des tirages (remplace celle du DSL) pour un placement reproductible
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Ajoute un agent à une position donnée.
+    /// Un agent déjà présent sur la case est remplacé.
     pub fn add_agent(&mut self, species_name: &str, pos: Position) -> Result<AgentId> {
         let species_info = self.species_map.get(species_name)
             .ok_or_else(|| anyhow!("Species '{}' not found", species_name))?
@@ -70,16 +93,20 @@ impl WorldBuilder {
         let agent_id = self.next_agent_id;
         self.next_agent_id += 1;
 
-        // Pour l'instant, on utilise 0 comme species_id, il faudra le mapper correctement
-        let agent = Agent::new(
+        if let Some(previous) = self.world.get(pos).and_then(|c| c.agent) {
+            self.agents[previous].kill();
+        }
+
+        let mut agent = Agent::new(
             agent_id,
             pos,
             species_info.kind,
-            0, // TODO: mapper correctement species_id
+            species_info.species_id,
             species_info.default_status,
             species_info.color,
             species_info.var_names.len(),
         );
+        agent.vars = species_info.init_values;
 
         self.agents.push(agent);
         self.world.set_agent(pos, Some(agent_id));
@@ -87,15 +114,16 @@ impl WorldBuilder {
         Ok(agent_id)
     }
 
-    /// Ajoute des agents en grille selon un pattern
+    /// Ajoute des agents en grille selon un pattern.
+    /// Les bornes de fin sont exclusives, comme dans le DSL.
     pub fn add_agents_pattern(&mut self, species_name: &str, pattern: &str, bounds: (usize, usize, usize, usize)) -> Result<()> {
         let (row_start, row_end, col_start, col_end) = bounds;
         
         // Parse le pattern (simplifié pour l'exemple)
         let pattern_parts: Vec<&str> = pattern.trim_matches(['(', ')']).split(',').collect();
         
-        for row in row_start..=row_end {
-            for col in col_start..=col_end {
+        for row in row_start..row_end {
+            for col in col_start..col_end {
                 // Pour l'instant, logique simple: si le pattern contient le nom de l'espèce, on place
                 if pattern_parts.iter().any(|&p| p.trim() == species_name) {
                     let pos = Position::new(row, col);
@@ -123,117 +151,111 @@ impl WorldBuilder {
         Ok(())
     }
     
-    /// Parse une expression de position et place les agents correspondants
+    /// Parse une expression de position et place les agents correspondants.
+    ///
+    /// Chaque axe est soit un indice (`5`), soit une range `start:end[:step]`
+    /// dont la fin est exclusive : `(0:16,8)`, `(7:10:2,5:11:5)`.
     fn parse_and_place_position(&mut self, species_pattern: &str, position_expr: &str) -> Result<()> {
-        if position_expr.contains(':') {
-            // Format de range: (start:end,start:end)
-            self.parse_range_position(species_pattern, position_expr)
-        } else if position_expr.starts_with('(') && position_expr.ends_with(')') {
-            // Position unique: (row,col)
-            self.parse_single_position(species_pattern, position_expr)
-        } else {
-            println!("Format de position non reconnu: {}", position_expr);
-            Ok(())
-        }
-    }
-    
-    /// Parse une position unique comme (5,10)
-    fn parse_single_position(&mut self, species_pattern: &str, position_expr: &str) -> Result<()> {
-        let content = position_expr.trim_matches(['(', ')']);
-        let parts: Vec<&str> = content.split(',').collect();
-        
-        if parts.len() == 2 {
-            if let (Ok(row), Ok(col)) = (parts[0].trim().parse::<usize>(), parts[1].trim().parse::<usize>()) {
-                let pos = Position::new(row, col);
-                if self.world.in_bounds(pos) {
-                    // Déterminer l'espèce à partir du pattern
-                    let species_name = self.resolve_species_from_pattern(species_pattern)?;
-                    self.add_agent(&species_name, pos)?;
-                    println!("Agent {} placé à ({}, {})", species_name, row, col);
+        let (rows, cols) = self.parse_position(position_expr)?;
+        let choices = self.parse_species_pattern(species_pattern)?;
+
+        let mut placed_count = 0;
+        for &row in &rows {
+            for &col in &cols {
+                // tirage uniforme parmi les entrées du pattern, `None` laisse la case vide
+                let choice = choices.choose(&mut self.rng).expect("pattern non vide");
+                if let Some(species_name) = choice {
+                    self.add_agent(species_name, Position::new(row, col))?;
+                    placed_count += 1;
                 }
             }
         }
+        println!("Placé {} agents avec pattern '{}' dans la zone {}", 
+                 placed_count, species_pattern, position_expr);
         Ok(())
     }
-    
-    /// Parse une range de positions comme (0:64,0:64)
-    fn parse_range_position(&mut self, species_pattern: &str, position_expr: &str) -> Result<()> {
-        let content = position_expr.trim_matches(['(', ')']);
+
+    /// Parse une position `(rows,cols)` en listes d'indices de lignes et de colonnes
+    fn parse_position(&self, position_expr: &str) -> Result<(Vec<usize>, Vec<usize>)> {
+        let content = position_expr.strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .ok_or_else(|| anyhow!("Format de position invalide: {}", position_expr))?;
         let parts: Vec<&str> = content.split(',').collect();
-        
-        if parts.len() == 2 {
-            let row_range = self.parse_range_part(parts[0].trim())?;
-            let col_range = self.parse_range_part(parts[1].trim())?;
-            
-            let mut placed_count = 0;
-            for row in row_range.0..=row_range.1 {
-                for col in col_range.0..=col_range.1 {
-                    let pos = Position::new(row, col);
-                    if self.world.in_bounds(pos) {
-                        // Pour un pattern complexe, on utilise une logique de placement aléatoire/pattern
-                        if self.should_place_at_position(species_pattern, pos)? {
-                            let species_name = self.resolve_species_from_pattern(species_pattern)?;
-                            self.add_agent(&species_name, pos)?;
-                            placed_count += 1;
-                        }
-                    }
-                }
-            }
-            println!("Placé {} agents avec pattern '{}' dans la zone {}", 
-                     placed_count, species_pattern, position_expr);
+        if parts.len() != 2 {
+            return Err(anyhow!("Format de position invalide: {}", position_expr));
         }
-        Ok(())
+
+        let rows = self.parse_range_part(parts[0].trim(), self.world.rows)?;
+        let cols = self.parse_range_part(parts[1].trim(), self.world.cols)?;
+        Ok((rows, cols))
     }
     
-    /// Parse une partie de range comme "0:64" ou "20:25"
-    fn parse_range_part(&self, range_str: &str) -> Result<(usize, usize)> {
+    /// Parse un axe comme "8", "0:64" ou "7:10:2" (fin exclusive).
+    /// Un indice hors de la grille est une erreur.
+    fn parse_range_part(&self, range_str: &str, size: usize) -> Result<Vec<usize>> {
         let parts: Vec<&str> = range_str.split(':').collect();
-        if parts.len() == 2 {
-            let start = parts[0].parse::<usize>()?;
-            let end = parts[1].parse::<usize>()?;
-            Ok((start, end))
-        } else {
-            Err(anyhow!("Format de range invalide: {}", range_str))
+        let number = |s: &str| s.trim().parse::<usize>()
+            .map_err(|_| anyhow!("Format de range invalide: {}", range_str));
+
+        let (start, end, step) = match parts.len() {
+            1 => {
+                let index = number(parts[0])?;
+                if index >= size {
+                    return Err(anyhow!("Range {} hors de la grille (taille {})", range_str, size));
+                }
+                (index, index + 1, 1)
+            }
+            2 => (number(parts[0])?, number(parts[1])?, 1),
+            3 => (number(parts[0])?, number(parts[1])?, number(parts[2])?),
+            _ => return Err(anyhow!("Format de range invalide: {}", range_str)),
+        };
+
+        if step == 0 {
+            return Err(anyhow!("Pas de range nul: {}", range_str));
         }
-    }
-    
-    /// Détermine si on doit placer un agent à cette position selon le pattern
-    fn should_place_at_position(&self, species_pattern: &str, _pos: Position) -> Result<bool> {
-        // Logique simplifiée pour les patterns complexes
-        if species_pattern.contains("void") {
-            // Pattern sparse avec des vides
-            Ok(rand::random::<f32>() < 0.3) // 30% de chance
-        } else {
-            // Pattern simple
-            Ok(true)
+        if start >= end {
+            return Err(anyhow!("Range vide: {}", range_str));
+        }
+        if end > size {
+            return Err(anyhow!("Range {} hors de la grille (taille {})", range_str, size));
         }
+        Ok((start..end).step_by(step).collect())
     }
     
-    /// Résout le nom d'espèce à partir d'un pattern
-    fn resolve_species_from_pattern(&self, species_pattern: &str) -> Result<String> {
-        // Si c'est un nom simple d'espèce
-        if self.species_map.contains_key(species_pattern) {
-            return Ok(species_pattern.to_string());
-        }
-        
-        // Si c'est un pattern complexe, extraire une espèce valide
-        for species_name in self.species_map.keys() {
-            if species_pattern.contains(species_name) {
-                return Ok(species_name.clone());
+    /// Parse un pattern d'espèces : un nom simple (`tree`) ou un tuple
+    /// (`(void,tree,tree,tree)`) dont chaque entrée a la même probabilité.
+    /// `void` désigne une case vide, sauf si une espèce porte ce nom.
+    fn parse_species_pattern(&self, species_pattern: &str) -> Result<Vec<Option<String>>> {
+        let content = species_pattern.strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .unwrap_or(species_pattern);
+
+        content.split(',').map(|entry| {
+            let name = entry.trim();
+            if self.species_map.contains_key(name) {
+                Ok(Some(name.to_string()))
+            } else if name == "void" {
+                Ok(None)
+            } else {
+                Err(anyhow!("Espèce '{}' inconnue dans le pattern: {}", name, species_pattern))
             }
-        }
-        
-        Err(anyhow!("Aucune espèce reconnue dans le pattern: {}", species_pattern))
+        }).collect()
     }
 
     /// Construit et retourne le monde final
     pub fn build(self) -> (World, Vec<Agent>) {
         (self.world, self.agents)
     }
+
+    /// Comme `build`, mais rend aussi le générateur pour que l'engine
+    /// poursuive la même séquence aléatoire que le placement
+    pub fn build_with_rng(self) -> (World, Vec<Agent>, StdRng) {
+        (self.world, self.agents, self.rng)
+    }
 }
 
 /// Parse une couleur depuis une chaîne (hex ou nom de couleur)
-fn parse_color(color_str: &str) -> Result<u32> {
+pub(crate) fn parse_color(color_str: &str) -> Result<u32> {
     // Si c'est un nom de couleur connu
     match color_str.to_lowercase().as_str() {
         "white" => Ok(0xFFFFFF),
@@ -16,6 +16,19 @@ impl Position {
         self.row * cols + self.col
     }
 
+    /// Voisinage de von Neumann (4 directions)
+    pub fn neighbors4(&self, rows: usize, cols: usize) -> Vec<Position> {
+        let mut res = Vec::with_capacity(4);
+        for (dr, dc) in [(-1, 0), (0, -1), (0, 1), (1, 0)] {
+            let rr = self.row as isize + dr;
+            let cc = self.col as isize + dc;
+            if rr >= 0 && cc >= 0 && rr < rows as isize && cc < cols as isize {
+                res.push(Position::new(rr as usize, cc as usize));
+            }
+        }
+        res
+    }
+
     /// Voisinage de Moore (8 directions)
     pub fn neighbors8(&self, rows: usize, cols: usize) -> Vec<Position> {
         let mut res = Vec::with_capacity(8);
@@ -1,12 +1,13 @@
-/// Système de logging pour OSCARv2
-/// 
-/// Ce module fournit des fonctionnalités de logging structurées pour le simulateur :
-/// - Niveaux de log configurables (Debug, Info, Warning, Error)
-/// - Formatage cohérent des messages
-/// - Support pour les statistiques de simulation
-/// - Logging conditionnel selon le niveau de verbosité
+//! Système de logging pour OSCARv2
+//!
+//! Ce module fournit des fonctionnalités de logging structurées pour le simulateur :
+//! - Niveaux de log configurables (Debug, Info, Warning, Error)
+//! - Formatage cohérent des messages
+//! - Support pour les statistiques de simulation
+//! - Logging conditionnel selon le niveau de verbosité
 
 use std::fmt;
+use std::sync::OnceLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Niveaux de log disponibles
@@ -31,6 +32,17 @@ impl fmt::Display for LogLevel {
 }
 
 impl LogLevel {
+    /// Nom utilisé en ligne de commande
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "debug" => Some(LogLevel::Debug),
+            "info" => Some(LogLevel::Info),
+            "warning" => Some(LogLevel::Warning),
+            "error" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+
     /// Retourne le symbole emoji associé au niveau
     pub fn emoji(&self) -> &'static str {
         match self {
@@ -144,21 +156,18 @@ impl Logger {
     }
 }
 
-// Instance globale du logger
-static mut GLOBAL_LOGGER: Option<Logger> = None;
+// Instance globale du logger, partagée entre threads
+static GLOBAL_LOGGER: OnceLock<Logger> = OnceLock::new();
 
-/// Initialise le logger global
+/// Initialise le logger global.
+/// Sans effet si le logger a déjà été initialisé ou utilisé.
 pub fn init_logger(logger: Logger) {
-    unsafe {
-        GLOBAL_LOGGER = Some(logger);
-    }
+    let _ = GLOBAL_LOGGER.set(logger);
 }
 
 /// Récupère le logger global ou crée un logger par défaut
 fn get_logger() -> &'static Logger {
-    unsafe {
-        GLOBAL_LOGGER.get_or_insert_with(Logger::default)
-    }
+    GLOBAL_LOGGER.get_or_init(Logger::default)
 }
 
 /// Macros pour simplifier l'utilisation
@@ -277,6 +286,13 @@ mod tests {
         logger.log(LogLevel::Error, "Message error");
     }
 
+    #[test]
+    fn test_level_from_name() {
+        assert_eq!(LogLevel::from_name("debug"), Some(LogLevel::Debug));
+        assert_eq!(LogLevel::from_name("error"), Some(LogLevel::Error));
+        assert_eq!(LogLevel::from_name("verbose"), None);
+    }
+
     #[test]
     fn test_format_message() {
         let logger = Logger::new()
@@ -0,0 +1,324 @@
+use OSCARv2::dsl::ast::{
+    CompareOp, ConfigAst, Decay, FieldDef, FieldKind, Metric, SensorDef, SensorField, Species,
+    SpeciesKind as DslSpeciesKind, StatusRule, Value, VarDef, World as WorldDSL,
+};
+use OSCARv2::dsl::compile::compile;
+use OSCARv2::engine::engine::VarInit;
+use OSCARv2::model::agent::SpeciesKind;
+
+fn species(kind: DslSpeciesKind, name: &str, color: &str) -> Species {
+    Species {
+        kind,
+        name: name.to_string(),
+        color: color.to_string(),
+        vars: vec![],
+        statuses: vec![],
+        births: vec![],
+        fields: vec![],
+        sensors: vec![],
+        blocks: vec![],
+    }
+}
+
+fn var(name: &str, init: i32, timestep: i32) -> VarDef {
+    VarDef { name: name.to_string(), init_value: Value::Int(init), timestep }
+}
+
+fn field(name: &str, step: i32) -> FieldDef {
+    FieldDef {
+        name: name.to_string(),
+        step,
+        metric: Metric::Chebyshev,
+        decay: Decay::Linear,
+        kind: FieldKind::Instant,
+    }
+}
+
+fn config(species: Vec<Species>) -> ConfigAst {
+    ConfigAst {
+        world: Some(WorldDSL { cols: 8, rows: 8, color: "FFF".to_string() }),
+        species,
+        agents: vec![],
+        seed: None,
+        mode: None,
+    }
+}
+
+fn forest() -> ConfigAst {
+    let mut tree = species(DslSpeciesKind::Mineral, "tree", "0F0");
+    tree.vars.push(var("hot", 0, 0));
+    tree.sensors.push(SensorDef {
+        name: "hot".to_string(),
+        fields: vec![SensorField { field: "flame".to_string(), sensitivity: 1.0 }],
+    });
+    tree.statuses.push(StatusRule {
+        variable: Some("hot".to_string()),
+        op: Some(CompareOp::Greater),
+        threshold: Some(1),
+        new_status: "fire".to_string(),
+    });
+
+    let mut fire = species(DslSpeciesKind::Mineral, "fire", "F00");
+    fire.vars.push(var("burning", 3, -1));
+    fire.vars.push(var("flame", 2, 0));
+    fire.fields.push(field("flame", -1));
+
+    config(vec![tree, fire])
+}
+
+#[test]
+fn test_compile_assigns_species_ids_in_declaration_order() {
+    let defs = compile(&forest()).expect("Failed to compile");
+
+    assert_eq!(defs.len(), 2);
+    assert_eq!(defs[0].species_id, 0);
+    assert_eq!(defs[0].name, "tree");
+    assert_eq!(defs[0].kind, SpeciesKind::Mineral);
+    assert_eq!(defs[0].color, 0x00FF00);
+    assert_eq!(defs[1].species_id, 1);
+    assert_eq!(defs[1].name, "fire");
+    assert_eq!(defs[1].color, 0xFF0000);
+}
+
+#[test]
+fn test_compile_vars() {
+    let defs = compile(&forest()).expect("Failed to compile");
+    let fire = &defs[1];
+
+    assert_eq!(fire.num_vars(), 2);
+    assert_eq!(fire.vars[0].name, "burning");
+    assert_eq!(fire.vars[0].init, VarInit::Value(3));
+    assert_eq!(fire.vars[0].timestep, -1);
+    assert_eq!(fire.var_index("flame"), Some(1));
+    assert_eq!(fire.var_index("missing"), None);
+}
+
+#[test]
+fn test_compile_fields_and_sensors() {
+    let defs = compile(&forest()).expect("Failed to compile");
+
+    let fire = &defs[1];
+    assert_eq!(fire.field_defs.len(), 1);
+    assert_eq!(fire.field_defs[0].name, "flame");
+    assert_eq!(fire.field_defs[0].step, 1);
+
+    let tree = &defs[0];
+    assert_eq!(tree.sensors.len(), 1);
+    assert_eq!(tree.sensors[0].target_var, 0);
+    assert_eq!(tree.sensors[0].terms.len(), 1);
+    assert_eq!(tree.sensors[0].terms[0].field_name, "flame");
+    assert_eq!(tree.sensors[0].terms[0].weight, 1.0);
+    assert_eq!(tree.status_rules.len(), 1);
+}
+
+#[test]
+fn test_compile_rejects_sensor_on_undeclared_var() {
+    let mut config = forest();
+    config.species[0].sensors[0].name = "cold".to_string();
+
+    let err = compile(&config).unwrap_err();
+    assert!(format!("{}", err).contains("Sensor variable 'cold'"));
+}
+
+#[test]
+fn test_compile_rejects_sensor_on_unknown_field() {
+    let mut config = forest();
+    config.species[0].sensors[0].fields[0].field = "smoke".to_string();
+
+    let err = compile(&config).unwrap_err();
+    assert!(format!("{}", err).contains("unknown field 'smoke'"));
+}
+
+#[test]
+fn test_compile_rejects_field_without_variable() {
+    let mut config = forest();
+    config.species[1].vars.pop();
+
+    let err = compile(&config).unwrap_err();
+    assert!(format!("{}", err).contains("has no matching variable"));
+}
+
+#[test]
+fn test_compile_rejects_inconsistent_field_steps() {
+    let mut config = forest();
+    let mut ember = species(DslSpeciesKind::Mineral, "ember", "F80");
+    ember.vars.push(var("flame", 1, 0));
+    ember.fields.push(field("flame", -2));
+    config.species.push(ember);
+
+    let err = compile(&config).unwrap_err();
+    assert!(format!("{}", err).contains("Field 'flame' declared with steps"));
+}
+
+#[test]
+fn test_compile_rejects_duplicate_species() {
+    let config = config(vec![
+        species(DslSpeciesKind::Mineral, "tree", "0F0"),
+        species(DslSpeciesKind::Mineral, "tree", "F00"),
+    ]);
+
+    let err = compile(&config).unwrap_err();
+    assert!(format!("{}", err).contains("declared twice"));
+}
+
+#[test]
+fn test_compile_inherited_var() {
+    let mut sheep = species(DslSpeciesKind::Animal, "sheep", "FF5");
+    sheep.vars.push(var("grass", 40, -1));
+    let mut hungry = species(DslSpeciesKind::Animal, "hungry", "F50");
+    hungry.vars.push(VarDef {
+        name: "grass".to_string(),
+        init_value: Value::Str("grass".to_string()),
+        timestep: -1,
+    });
+
+    let defs = compile(&config(vec![sheep, hungry])).expect("Failed to compile");
+
+    assert_eq!(defs[1].vars[0].init, VarInit::Inherit("grass".to_string()));
+    assert_eq!(defs[1].initial_vars(Some((&defs[0], &[12]))), vec![12]);
+}
+
+#[test]
+fn test_compile_rejects_inherited_var_undeclared_everywhere() {
+    let mut sheep = species(DslSpeciesKind::Animal, "sheep", "FF5");
+    sheep.vars.push(VarDef {
+        name: "grass".to_string(),
+        init_value: Value::Str("grss".to_string()),
+        timestep: -1,
+    });
+
+    let err = compile(&config(vec![sheep])).unwrap_err();
+    assert!(format!("{}", err).contains("Variable 'grass' of species 'sheep' inherits undeclared variable 'grss'"));
+}
+
+#[test]
+fn test_compile_bundled_worlds() {
+    use OSCARv2::dsl::parser::parse_file;
+
+    for file_path in [
+        "worlds/oscar1.txt",
+        "worlds/oscar2.txt",
+        "worlds/oscar3.txt",
+        "worlds/oscar4.txt",
+        "worlds/oscar5.txt",
+        "worlds/oscar6.txt",
+        "worlds/oscar6_trace.txt",
+        "worlds/oscar7.txt",
+    ] {
+        let content = std::fs::read_to_string(file_path).unwrap();
+        let config = parse_file(&content).unwrap();
+        let defs = compile(&config);
+        assert!(defs.is_ok(), "Failed to compile {}: {:?}", file_path, defs.err());
+    }
+
+    // oscarc lit un champ `light_blue` qu'aucune espèce n'émet
+    let content = std::fs::read_to_string("worlds/oscarc.txt").unwrap();
+    let config = parse_file(&content).unwrap();
+    assert!(compile(&config).is_err());
+}
+
+#[test]
+fn test_compile_block_on_unknown_field() {
+    let mut config = forest();
+    config.species[0].blocks.push("smoke".to_string());
+
+    let err = compile(&config).unwrap_err();
+    assert!(format!("{}", err).contains("blocks unknown field 'smoke'"));
+
+    config.species[0].blocks[0] = "flame".to_string();
+    let defs = compile(&config).expect("Failed to compile");
+    assert_eq!(defs[0].blocks, vec!["flame"]);
+}
+
+#[test]
+fn test_compile_status_targets() {
+    let mut config = forest();
+    config.species[0].statuses[0].new_status = "smoke".to_string();
+    let err = compile(&config).unwrap_err();
+    assert!(format!("{}", err).contains("switches to unknown status 'smoke'"));
+
+    // les statuts prédéfinis n'ont pas besoin d'être déclarés
+    for builtin in ["void", "end", "trace"] {
+        config.species[0].statuses[0].new_status = builtin.to_string();
+        assert!(compile(&config).is_ok(), "{} should compile", builtin);
+    }
+}
+
+#[test]
+fn test_compile_resolves_rule_variables() {
+    use OSCARv2::dsl::ast::BirthRule;
+    use OSCARv2::engine::rules::Condition;
+
+    let mut config = forest();
+    config.species[1].statuses.push(StatusRule {
+        variable: Some("flame".to_string()),
+        op: Some(CompareOp::Less),
+        threshold: Some(1),
+        new_status: "void".to_string(),
+    });
+    config.species[1].births.push(BirthRule {
+        variable: Some("burning".to_string()),
+        op: Some(CompareOp::Greater),
+        threshold: Some(2),
+        child_status: "fire".to_string(),
+    });
+
+    let defs = compile(&config).expect("Failed to compile");
+    let fire = &defs[1];
+    assert_eq!(fire.status_rules[0].condition, Some(Condition { var: 1, op: CompareOp::Less, threshold: 1 }));
+    assert_eq!(fire.birth_rules[0].condition, Some(Condition { var: 0, op: CompareOp::Greater, threshold: 2 }));
+    assert_eq!(fire.birth_rules[0].child_status, "fire");
+
+    config.species[1].births[0].variable = Some("heat".to_string());
+    let err = compile(&config).unwrap_err();
+    assert!(format!("{}", err).contains("Rule variable 'heat' not declared in species 'fire'"));
+}
+
+#[test]
+fn test_compile_field_step_sign_convention() {
+    let mut config = forest();
+    config.species[1].fields[0].step = 0;
+    let defs = compile(&config).expect("Failed to compile");
+    assert_eq!(defs[1].field_defs[0].step, 0);
+
+    config.species[1].fields[0].step = 2;
+    let err = compile(&config).unwrap_err();
+    assert!(format!("{}", err).contains("Field 'flame' of species 'fire' has positive step 2"));
+}
+
+#[test]
+fn test_compile_field_metric_and_decay() {
+    let mut config = forest();
+    config.species[1].fields[0].metric = Metric::Euclidean;
+    config.species[1].fields[0].decay = Decay::Gaussian;
+    let defs = compile(&config).expect("Failed to compile");
+    assert_eq!(defs[1].field_defs[0].metric, Metric::Euclidean);
+    assert_eq!(defs[1].field_defs[0].decay, Decay::Gaussian);
+
+    let mut ember = species(DslSpeciesKind::Mineral, "ember", "F80");
+    ember.vars.push(var("flame", 1, 0));
+    ember.fields.push(FieldDef { metric: Metric::Euclidean, ..field("flame", -1) });
+    config.species.push(ember);
+    let err = compile(&config).unwrap_err();
+    assert!(format!("{}", err).contains("Field 'flame' declared with profiles"));
+}
+
+#[test]
+fn test_compile_persistent_fields() {
+    let mut config = forest();
+    config.species[1].fields[0].kind = FieldKind::Persistent { diffusion: 0.2, evaporation: 0.1 };
+    let defs = compile(&config).expect("Failed to compile");
+    assert_eq!(defs[1].field_defs[0].kind, FieldKind::Persistent { diffusion: 0.2, evaporation: 0.1 });
+
+    let mut ember = species(DslSpeciesKind::Mineral, "ember", "F80");
+    ember.vars.push(var("flame", 1, 0));
+    ember.fields.push(field("flame", -1));
+    config.species.push(ember);
+    let err = compile(&config).unwrap_err();
+    assert!(format!("{}", err).contains("Field 'flame' declared with kinds"));
+
+    config.species.pop();
+    config.species[1].fields[0].kind = FieldKind::Persistent { diffusion: 1.5, evaporation: 0.1 };
+    let err = compile(&config).unwrap_err();
+    assert!(format!("{}", err).contains("rates must lie between 0 and 1"));
+}
@@ -0,0 +1,316 @@
+use OSCARv2::dsl::ast::{Decay, FieldKind, Metric};
+use OSCARv2::engine::field::{Field, FieldDef};
+use OSCARv2::model::position::Position;
+
+fn field(step: i32, rows: usize, cols: usize) -> Field {
+    Field::new(FieldDef { name: "smell".to_string(), step, ..Default::default() }, rows, cols)
+}
+
+fn shaped(step: i32, metric: Metric, decay: Decay, size: usize) -> Field {
+    Field::new(FieldDef { name: "light".to_string(), step, metric, decay, ..Default::default() }, size, size)
+}
+
+#[test]
+fn test_add_source_chebyshev_square() {
+    let mut f = field(1, 7, 7);
+    f.add_source(Position::new(3, 3), 3);
+
+    assert_eq!(f.get(Position::new(3, 3)), 3);
+    assert_eq!(f.get(Position::new(2, 2)), 2);
+    assert_eq!(f.get(Position::new(3, 5)), 1);
+    assert_eq!(f.get(Position::new(1, 5)), 1);
+    assert_eq!(f.get(Position::new(0, 3)), 0);
+}
+
+#[test]
+fn test_sources_superpose() {
+    let mut f = field(1, 5, 5);
+    f.add_source(Position::new(2, 1), 2);
+    f.add_source(Position::new(2, 3), 2);
+
+    assert_eq!(f.get(Position::new(2, 2)), 2);
+    assert_eq!(f.get(Position::new(2, 1)), 2);
+
+    f.clear();
+    assert!(f.values.iter().all(|&v| v == 0));
+}
+
+#[test]
+fn test_wall_stops_propagation() {
+    // mur vertical en colonne 2, sauf en haut (ligne 0)
+    let mut f = field(1, 5, 5);
+    for row in 1..5 {
+        f.block(Position::new(row, 2));
+    }
+    f.add_source(Position::new(3, 1), 4);
+
+    // le mur lui-même ne reçoit rien
+    assert!(f.is_blocked(Position::new(3, 2)));
+    assert_eq!(f.get(Position::new(3, 2)), 0);
+    // côté source : inchangé
+    assert_eq!(f.get(Position::new(3, 1)), 4);
+    assert_eq!(f.get(Position::new(2, 0)), 3);
+    // derrière le mur, le champ doit contourner par la ligne 0
+    // chemin (3,1) -> (2,1) -> (1,1) -> (0,2) -> (1,3) : distance 4
+    assert_eq!(f.get(Position::new(0, 2)), 1);
+    assert_eq!(f.get(Position::new(1, 3)), 0);
+    assert_eq!(f.get(Position::new(3, 3)), 0);
+}
+
+#[test]
+fn test_closed_wall_shelters_cells() {
+    let mut f = field(1, 5, 5);
+    for row in 0..5 {
+        f.block(Position::new(row, 2));
+    }
+    f.add_source(Position::new(2, 0), 9);
+
+    for row in 0..5 {
+        for col in 2..5 {
+            assert_eq!(f.get(Position::new(row, col)), 0);
+        }
+    }
+    assert_eq!(f.get(Position::new(4, 1)), 7);
+
+    // sans obstacle, la propagation redevient un carré
+    f.clear();
+    f.clear_obstacles();
+    f.add_source(Position::new(2, 0), 9);
+    assert_eq!(f.get(Position::new(2, 4)), 5);
+}
+
+#[test]
+fn test_remove_source_undoes_add_source() {
+    let mut f = field(1, 5, 5);
+    f.add_source(Position::new(1, 1), 3);
+    let before = f.values.clone();
+    f.add_source(Position::new(3, 3), 2);
+    f.remove_source(Position::new(3, 3), 2);
+    assert_eq!(f.values, before);
+
+    // idem autour d'obstacles
+    f.clear();
+    f.block(Position::new(2, 2));
+    f.add_source(Position::new(2, 1), 4);
+    f.remove_source(Position::new(2, 1), 4);
+    assert!(f.values.iter().all(|&v| v == 0));
+}
+
+#[test]
+fn test_contribution_matches_stamp() {
+    let mut f = field(1, 5, 5);
+    let source = Position::new(2, 2);
+    f.add_source(source, 3);
+
+    for row in 0..5 {
+        for col in 0..5 {
+            let at = Position::new(row, col);
+            assert_eq!(f.contribution(source, 3, at), f.get(at));
+        }
+    }
+    assert_eq!(f.contribution(source, 0, source), 0);
+}
+
+#[test]
+fn test_zero_step_covers_whole_grid() {
+    let mut f = field(0, 6, 4);
+    f.add_source(Position::new(0, 0), 3);
+    assert!(f.values.iter().all(|&v| v == 3));
+
+    f.block(Position::new(2, 2));
+    f.clear();
+    f.add_source(Position::new(5, 3), 2);
+    assert_eq!(f.get(Position::new(0, 0)), 2);
+    assert_eq!(f.get(Position::new(2, 2)), 0);
+}
+
+#[test]
+fn test_values_never_negative() {
+    let mut f = field(2, 9, 9);
+    f.add_source(Position::new(4, 4), 5);
+    assert_eq!(f.get(Position::new(4, 5)), 3);
+    assert_eq!(f.get(Position::new(4, 6)), 1);
+    assert!(f.values.iter().all(|&v| v >= 0));
+    assert_eq!(f.get(Position::new(4, 8)), 0);
+}
+
+#[test]
+fn test_metrics_shape_the_field() {
+    let center = Position::new(4, 4);
+
+    let mut f = shaped(1, Metric::Manhattan, Decay::Linear, 9);
+    f.add_source(center, 3);
+    assert_eq!(f.get(Position::new(4, 6)), 1);
+    assert_eq!(f.get(Position::new(3, 3)), 1);
+    assert_eq!(f.get(Position::new(2, 3)), 0);
+
+    let mut f = shaped(1, Metric::Euclidean, Decay::Linear, 9);
+    f.add_source(center, 3);
+    assert_eq!(f.get(Position::new(4, 6)), 1);
+    assert_eq!(f.get(Position::new(3, 3)), 2); // 3 - 1.41 arrondi
+    assert_eq!(f.get(Position::new(2, 2)), 0); // 3 - 2.83 arrondi
+}
+
+#[test]
+fn test_decay_profiles() {
+    let def = |decay| FieldDef { name: "light".to_string(), step: 1, metric: Metric::Chebyshev, decay, ..Default::default() };
+
+    // x = dist * step / valeur = 0.5
+    assert_eq!(def(Decay::Linear).value_at(8, 4.0), 4);
+    assert_eq!(def(Decay::Exponential).value_at(8, 4.0), 5); // 8 e^-0.5
+    assert_eq!(def(Decay::Gaussian).value_at(8, 4.0), 6); // 8 e^-0.25
+    assert_eq!(def(Decay::InverseSquare).value_at(8, 4.0), 4); // 8 / 1.5²
+
+    for decay in [Decay::Linear, Decay::Exponential, Decay::Gaussian, Decay::InverseSquare] {
+        assert_eq!(def(decay).value_at(8, 0.0), 8);
+        assert_eq!(def(decay).value_at(0, 0.0), 0);
+        // valeurs décroissantes avec la distance et jamais négatives
+        let values: Vec<i32> = (0..40).map(|d| def(decay).value_at(8, d as f32)).collect();
+        assert!(values.windows(2).all(|w| w[0] >= w[1]), "{:?}", decay);
+        assert_eq!(*values.last().unwrap(), 0, "{:?}", decay);
+    }
+}
+
+#[test]
+fn test_shaped_fields_are_reversible_and_match_contribution() {
+    for metric in [Metric::Chebyshev, Metric::Manhattan, Metric::Euclidean] {
+        for decay in [Decay::Linear, Decay::Exponential, Decay::Gaussian, Decay::InverseSquare] {
+            let mut f = shaped(1, metric, decay, 7);
+            let source = Position::new(2, 3);
+            f.add_source(source, 4);
+            for row in 0..7 {
+                for col in 0..7 {
+                    let at = Position::new(row, col);
+                    assert_eq!(f.contribution(source, 4, at), f.get(at), "{:?} {:?}", metric, decay);
+                }
+            }
+            f.remove_source(source, 4);
+            assert!(f.values.iter().all(|&v| v == 0));
+
+            f.block(Position::new(2, 4));
+            f.add_source(source, 4);
+            f.remove_source(source, 4);
+            assert!(f.values.iter().all(|&v| v == 0));
+        }
+    }
+}
+
+#[test]
+fn test_manhattan_wall_detour() {
+    // mur vertical en colonne 2, sauf en haut (ligne 0)
+    let mut f = shaped(1, Metric::Manhattan, Decay::Linear, 5);
+    for row in 1..5 {
+        f.block(Position::new(row, 2));
+    }
+    f.add_source(Position::new(1, 1), 5);
+    // chemin (1,1) -> (0,1) -> (0,2) -> (0,3) -> (1,3) : distance 4
+    assert_eq!(f.get(Position::new(1, 3)), 1);
+    assert_eq!(f.get(Position::new(2, 3)), 0);
+}
+
+fn persistent(diffusion: f32, evaporation: f32) -> Field {
+    let kind = FieldKind::Persistent { diffusion, evaporation };
+    // un pas égal à la valeur déposée limite le dépôt à la case de l'agent
+    Field::new(FieldDef { name: "pheromone".to_string(), step: 16, kind, ..Default::default() }, 5, 5)
+}
+
+#[test]
+fn test_instant_field_tick_clears() {
+    let mut f = field(1, 5, 5);
+    f.add_source(Position::new(2, 2), 3);
+    f.tick();
+    assert!(f.values.iter().all(|&v| v == 0));
+}
+
+#[test]
+fn test_persistent_field_diffuses_and_evaporates() {
+    let center = Position::new(2, 2);
+    let mut f = persistent(0.5, 0.0);
+    f.add_source(center, 16);
+    f.tick();
+    // la moitié reste sur place, le reste part aux 8 voisines
+    assert_eq!(f.get(center), 8);
+    assert_eq!(f.get(Position::new(1, 1)), 1);
+    assert_eq!(f.get(Position::new(2, 3)), 1);
+    assert_eq!(f.get(Position::new(0, 0)), 0);
+    assert_eq!(f.values.iter().sum::<i32>(), 16);
+
+    let mut f = persistent(0.0, 0.25);
+    f.add_source(center, 16);
+    f.tick();
+    assert_eq!(f.get(center), 12);
+    f.tick();
+    assert_eq!(f.get(center), 9);
+    for _ in 0..20 {
+        f.tick();
+    }
+    assert!(f.values.iter().all(|&v| v == 0));
+}
+
+#[test]
+fn test_persistent_field_accumulates_deposits() {
+    let center = Position::new(2, 2);
+    let mut f = persistent(0.0, 0.5);
+    for _ in 0..3 {
+        f.add_source(center, 16);
+        f.tick();
+    }
+    // 16 * (1/2 + 1/4 + 1/8)
+    assert_eq!(f.get(center), 14);
+
+    // une source posée sur la mémoire s'y ajoute sans la modifier
+    f.add_source(center, 16);
+    assert_eq!(f.get(center), 30);
+    f.remove_source(center, 16);
+    f.tick();
+    assert_eq!(f.get(center), 7);
+
+    f.clear();
+    f.tick();
+    assert!(f.values.iter().all(|&v| v == 0));
+}
+
+#[test]
+fn test_obstacles_match() {
+    let mut f = field(1, 5, 5);
+    assert!(f.obstacles_match(&[]));
+
+    f.block(Position::new(1, 1));
+    f.block(Position::new(1, 1));
+    f.block(Position::new(3, 2));
+    assert!(f.obstacles_match(&[Position::new(3, 2), Position::new(1, 1)]));
+    assert!(!f.obstacles_match(&[Position::new(1, 1)]));
+    assert!(!f.obstacles_match(&[Position::new(1, 1), Position::new(3, 3)]));
+
+    f.clear_obstacles();
+    assert!(f.obstacles_match(&[]));
+}
+
+#[test]
+fn test_contribution_matches_stamp_around_obstacles() {
+    let source = Position::new(2, 2);
+    for metric in [Metric::Chebyshev, Metric::Manhattan, Metric::Euclidean] {
+        let mut f = shaped(1, metric, Decay::Linear, 6);
+        // les deux chemins orthogonaux vers (1,1) sont coupés
+        for wall in [Position::new(1, 2), Position::new(2, 1), Position::new(4, 3)] {
+            f.block(wall);
+        }
+        f.add_source(source, 5);
+
+        for row in 0..6 {
+            for col in 0..6 {
+                let at = Position::new(row, col);
+                assert_eq!(f.contribution(source, 5, at), f.get(at), "{:?} at {:?}", metric, at);
+            }
+        }
+    }
+
+    // en Manhattan, (1,1) n'est atteinte qu'en 6 pas : hors de portée
+    let mut f = shaped(1, Metric::Manhattan, Decay::Linear, 6);
+    f.block(Position::new(1, 2));
+    f.block(Position::new(2, 1));
+    f.add_source(source, 5);
+    assert_eq!(f.get(Position::new(1, 1)), 0);
+    assert_eq!(f.contribution(source, 5, Position::new(1, 1)), 0);
+    assert_eq!(f.contribution(source, 5, Position::new(1, 3)), 3);
+}
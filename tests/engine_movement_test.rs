@@ -0,0 +1,87 @@
+use OSCARv2::engine::movement::{resolve_moves, MoveIntent, MovePolicy};
+use OSCARv2::model::position::Position;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn intent(agent: usize, row: usize, col: usize, value: i32) -> MoveIntent {
+    MoveIntent { agent, target: Position::new(row, col), value }
+}
+
+fn winners(policy: MovePolicy, seed: u64) -> Vec<usize> {
+    let intents = vec![
+        intent(0, 1, 1, 2),
+        intent(1, 1, 1, 5),
+        intent(2, 1, 1, 3),
+        intent(3, 0, 0, 1),
+    ];
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut moved: Vec<usize> = resolve_moves(intents, policy, &mut rng)
+        .iter()
+        .map(|i| i.agent)
+        .collect();
+    moved.sort();
+    moved
+}
+
+#[test]
+fn test_one_winner_per_target() {
+    for policy in [MovePolicy::FirstCome, MovePolicy::HighestValue, MovePolicy::Lottery] {
+        for seed in 0..20 {
+            let moved = winners(policy, seed);
+            assert_eq!(moved.len(), 2);
+            // l'agent seul sur sa case passe toujours
+            assert!(moved.contains(&3));
+        }
+    }
+}
+
+#[test]
+fn test_highest_value_wins() {
+    for seed in 0..20 {
+        assert_eq!(winners(MovePolicy::HighestValue, seed), vec![1, 3]);
+    }
+}
+
+#[test]
+fn test_first_come_order_is_shuffled() {
+    // l'ordre d'arrivée est tiré au hasard : l'index de l'agent ne le favorise pas
+    let all: Vec<Vec<usize>> = (0..40).map(|seed| winners(MovePolicy::FirstCome, seed)).collect();
+    for agent in 0..3 {
+        assert!(all.iter().any(|moved| moved.contains(&agent)), "agent {} never wins", agent);
+    }
+    for seed in 0..20 {
+        assert_eq!(winners(MovePolicy::FirstCome, seed), winners(MovePolicy::FirstCome, seed));
+    }
+}
+
+#[test]
+fn test_lottery_depends_on_seed_only() {
+    let all: Vec<Vec<usize>> = (0..40).map(|seed| winners(MovePolicy::Lottery, seed)).collect();
+    // chaque prétendant finit par gagner pour une graine donnée
+    for agent in 0..3 {
+        assert!(all.iter().any(|moved| moved.contains(&agent)), "agent {} never wins", agent);
+    }
+    assert_eq!(winners(MovePolicy::Lottery, 7), winners(MovePolicy::Lottery, 7));
+}
+
+#[test]
+fn test_highest_value_ties_are_random() {
+    let intents = || vec![intent(0, 1, 1, 4), intent(1, 1, 1, 4), intent(2, 1, 1, 1)];
+    let mut won = [false; 3];
+    for seed in 0..40 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let moved = resolve_moves(intents(), MovePolicy::HighestValue, &mut rng);
+        assert_eq!(moved.len(), 1);
+        won[moved[0].agent] = true;
+    }
+    assert_eq!(won, [true, true, false]);
+}
+
+#[test]
+fn test_move_policy_from_name() {
+    assert_eq!(MovePolicy::from_name("first-come"), Some(MovePolicy::FirstCome));
+    assert_eq!(MovePolicy::from_name("highest-value"), Some(MovePolicy::HighestValue));
+    assert_eq!(MovePolicy::from_name("lottery"), Some(MovePolicy::Lottery));
+    assert_eq!(MovePolicy::from_name("fastest"), None);
+    assert_eq!(MovePolicy::default(), MovePolicy::FirstCome);
+}
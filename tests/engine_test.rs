@@ -0,0 +1,1095 @@
+use OSCARv2::dsl::ast::CompareOp;
+use OSCARv2::dsl::compile::compile;
+use OSCARv2::dsl::parser::parse_file;
+use OSCARv2::engine::engine::{Engine, SpeciesDef, VarInit, VarSpec};
+use OSCARv2::engine::field::FieldDef;
+use OSCARv2::engine::rules::{Condition, StatusRule};
+use OSCARv2::engine::sensor::{SensorDef, SensorTerm};
+use OSCARv2::model::agent::{Agent, SpeciesKind};
+use OSCARv2::model::builder::WorldBuilder;
+use OSCARv2::model::position::Position;
+use OSCARv2::model::world::World;
+use rand::Rng;
+
+fn var(name: &str, init: i32, timestep: i32) -> VarSpec {
+    VarSpec { name: name.to_string(), init: VarInit::Value(init), timestep }
+}
+
+fn species(vars: Vec<VarSpec>) -> SpeciesDef {
+    SpeciesDef {
+        species_id: 0,
+        name: "test".to_string(),
+        kind: SpeciesKind::Mineral,
+        color: 0x000000,
+        vars,
+        status_rules: vec![],
+        birth_rules: vec![],
+        field_defs: vec![],
+        sensors: vec![],
+        blocks: vec![],
+    }
+}
+
+fn spawn(world: &mut World, spec: &SpeciesDef, species_id: usize, pos: Position) -> usize {
+    let id = world.spawn_agent(pos, spec.kind, species_id, "test".to_string(), spec.color, spec.num_vars());
+    world.agents[id].vars = spec.initial_vars(None);
+    id
+}
+
+/// Construit un engine complet depuis un source DSL
+fn engine_from_dsl(src: &str) -> Engine {
+    let config = parse_file(src).expect("Failed to parse DSL");
+    let species_defs = compile(&config).expect("Failed to compile DSL");
+    let mut builder = WorldBuilder::from_config(&config).expect("Failed to build world");
+    builder.place_agents(&config).expect("Failed to place agents");
+    let (mut world, agents, rng) = builder.build_with_rng();
+    world.agents = agents;
+    Engine::new(world, species_defs)
+        .with_rng(rng)
+        .with_update_mode(config.mode.unwrap_or_default())
+}
+
+fn agent_at(engine: &Engine, row: usize, col: usize) -> &OSCARv2::model::agent::Agent {
+    let id = engine.world.get(Position::new(row, col)).unwrap().agent.expect("no agent");
+    &engine.world.agents[id]
+}
+
+#[test]
+fn test_step_emits_fields_and_updates_sensors() {
+    let mut heater = species(vec![var("heat", 3, 0)]);
+    heater.field_defs.push(FieldDef { name: "heat".to_string(), step: 1, ..Default::default() });
+
+    let mut probe = species(vec![var("warm", 0, 0)]);
+    probe.species_id = 1;
+    probe.sensors.push(SensorDef {
+        name: "warm".to_string(),
+        target_var: 0,
+        terms: vec![SensorTerm { field_name: "heat".to_string(), weight: 1.0 }],
+    });
+
+    let mut world = World::new(5, 5);
+    spawn(&mut world, &heater, 0, Position::new(2, 2));
+    let near = spawn(&mut world, &probe, 1, Position::new(2, 3));
+    let far = spawn(&mut world, &probe, 1, Position::new(0, 0));
+
+    let mut engine = Engine::new(world, vec![heater, probe]);
+    engine.step();
+
+    assert_eq!(engine.fields["heat"].get(Position::new(2, 2)), 3);
+    assert_eq!(engine.world.agents[near].get_var(0), 2);
+    assert_eq!(engine.world.agents[far].get_var(0), 1);
+}
+
+#[test]
+fn test_step_applies_timesteps() {
+    let spec = species(vec![var("seed", 3, -1), var("age", 0, 1), var("still", 7, 0)]);
+
+    let mut world = World::new(3, 3);
+    let id = spawn(&mut world, &spec, 0, Position::new(1, 1));
+
+    let mut engine = Engine::new(world, vec![spec]);
+    engine.step();
+    engine.step();
+
+    let agent = &engine.world.agents[id];
+    assert_eq!(agent.get_var(0), 1);
+    assert_eq!(agent.get_var(1), 2);
+    assert_eq!(agent.get_var(2), 7);
+}
+
+#[test]
+fn test_step_status_rule_runs_after_timesteps() {
+    let mut spec = species(vec![var("life", 2, -1)]);
+    spec.status_rules.push(StatusRule {
+        condition: Some(Condition { var: 0, op: CompareOp::Less, threshold: 1 }),
+        new_status: "void".to_string(),
+    });
+
+    let mut world = World::new(3, 3);
+    let pos = Position::new(0, 1);
+    let id = spawn(&mut world, &spec, 0, pos);
+
+    let mut engine = Engine::new(world, vec![spec]);
+    engine.step();
+    assert!(engine.world.agents[id].alive);
+
+    engine.step();
+    assert!(!engine.world.agents[id].alive);
+    assert!(engine.world.is_position_free(&pos));
+}
+
+#[test]
+fn test_step_does_nothing_when_stopped() {
+    let spec = species(vec![var("seed", 3, -1)]);
+
+    let mut world = World::new(2, 2);
+    let id = spawn(&mut world, &spec, 0, Position::new(0, 0));
+
+    let mut engine = Engine::new(world, vec![spec]);
+    engine.running = false;
+    engine.step();
+
+    assert_eq!(engine.world.agents[id].get_var(0), 3);
+}
+
+#[test]
+fn test_initial_vars_inherit_from_previous_species() {
+    let sheep = species(vec![var("grass", 40, -1)]);
+    let mut hungry = species(vec![
+        VarSpec { name: "grass".to_string(), init: VarInit::Inherit("grass".to_string()), timestep: -1 },
+        VarSpec { name: "wool".to_string(), init: VarInit::Inherit("wool".to_string()), timestep: 0 },
+        var("smell", 0, 0),
+    ]);
+    hungry.species_id = 1;
+
+    assert_eq!(hungry.initial_vars(Some((&sheep, &[24]))), vec![24, 0, 0]);
+    // sans statut précédent, une valeur héritée vaut 0
+    assert_eq!(hungry.initial_vars(None), vec![0, 0, 0]);
+}
+
+#[test]
+fn test_block_species_shelters_sensors() {
+    let mut engine = engine_from_dsl(r"
+        world 5 5 FFF
+
+        mineral stove F00
+        var heat 4
+        field heat -1
+
+        mineral wall 777
+        block heat
+
+        mineral probe 0F0
+        var warm
+        sensor warm heat 1
+
+        agent wall (0,2) (1,2) (2,2) (3,2) (4,2)
+        agent stove (2,0)
+        agent probe (2,1) (2,3)
+    ");
+    engine.step();
+
+    assert_eq!(agent_at(&engine, 2, 1).get_var(0), 3);
+    assert_eq!(agent_at(&engine, 2, 3).get_var(0), 0);
+    assert_eq!(engine.fields["heat"].get(Position::new(2, 2)), 0);
+}
+
+#[test]
+fn test_same_seed_gives_identical_runs() {
+    let src = r"
+        world 16 16 FFF
+        seed 42
+
+        mineral tree 0F0
+        var hot
+        sensor hot flame 1
+        status hot > 0 fire
+
+        mineral fire F00
+        var flame 2
+        var burning 3 -1
+        field flame -1
+        status burning < 1 ash
+
+        mineral ash 777
+
+        agent (void,tree,tree,tree) (0:16,0:16)
+        agent fire (8,8)
+    ";
+    let run = || {
+        let mut engine = engine_from_dsl(src);
+        for _ in 0..5 {
+            engine.step();
+        }
+        let state: Vec<_> = engine.world.agents.iter()
+            .map(|a| (a.pos, a.alive, a.status.clone(), a.vars.clone()))
+            .collect();
+        (state, engine.rng.random::<u64>())
+    };
+
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn test_animal_moves_toward_highest_sensor_value() {
+    let mut engine = engine_from_dsl(r"
+        world 5 5 FFF
+        seed 1
+
+        vegetal grass 0C0
+        var scent 4
+        field scent -1
+
+        animal sheep FF5
+        var smell
+        sensor smell scent 1
+
+        agent grass (2,0)
+        agent sheep (2,3)
+    ");
+    engine.step();
+
+    let sheep = engine.world.agents.iter().find(|a| a.status == "sheep").unwrap();
+    assert_eq!(sheep.pos.col, 2);
+    assert_eq!(engine.world.get(sheep.pos).unwrap().agent, Some(sheep.id));
+    assert!(engine.world.is_position_free(&Position::new(2, 3)));
+    // le végétal ne bouge pas
+    assert_eq!(engine.world.agents[0].pos, Position::new(2, 0));
+}
+
+#[test]
+fn test_animal_without_sensor_moves_randomly_under_seed() {
+    let src = r"
+        world 9 9 FFF
+        seed 5
+
+        animal sheep FF5
+
+        agent sheep (4,4)
+    ";
+    let run = || {
+        let mut engine = engine_from_dsl(src);
+        let mut path = vec![];
+        for _ in 0..6 {
+            let before = engine.world.agents[0].pos;
+            engine.step();
+            let after = engine.world.agents[0].pos;
+            assert!(before.row.abs_diff(after.row) <= 1 && before.col.abs_diff(after.col) <= 1);
+            assert_ne!(before, after);
+            path.push(after);
+        }
+        path
+    };
+
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn test_animal_stays_when_surrounded() {
+    let mut engine = engine_from_dsl(r"
+        world 3 3 FFF
+
+        mineral rock 777
+
+        animal sheep FF5
+
+        agent rock (0:3,0:3)
+        agent sheep (1,1)
+    ");
+    engine.step();
+
+    let sheep = agent_at(&engine, 1, 1);
+    assert_eq!(sheep.status, "sheep");
+}
+
+#[test]
+fn test_conflicting_moves_keep_grid_consistent() {
+    use OSCARv2::engine::movement::MovePolicy;
+
+    for policy in [MovePolicy::FirstCome, MovePolicy::HighestValue, MovePolicy::Lottery] {
+        // une seule case libre, visée par les deux moutons
+        let mut engine = engine_from_dsl(r"
+            world 3 1 FFF
+            seed 3
+
+            animal sheep FF5
+
+            agent sheep (0,0) (0,2)
+        ").with_move_policy(policy);
+        engine.step();
+
+        let moved = engine.world.agents.iter().filter(|a| a.pos == Position::new(0, 1)).count();
+        assert_eq!(moved, 1);
+        for agent in engine.world.agents.iter().filter(|a| a.alive) {
+            assert_eq!(engine.world.get(agent.pos).unwrap().agent, Some(agent.id));
+        }
+        let occupied = engine.world.grid.iter().filter(|c| c.agent.is_some()).count();
+        assert_eq!(occupied, 2);
+    }
+}
+
+/// Un poêle qui s'éteint au premier step, à côté d'une sonde
+fn dying_stove(mode: &str, seed: u64) -> Engine {
+    engine_from_dsl(&format!(r"
+        world 3 1 FFF
+        seed {seed}
+        mode {mode}
+
+        mineral stove F00
+        var heat 3 -3
+        field heat -1
+        status heat < 1 void
+
+        mineral probe 0F0
+        var warm
+        sensor warm heat 1
+
+        agent stove (0,0)
+        agent probe (0,1)
+    "))
+}
+
+#[test]
+fn test_sync_mode_reads_previous_state() {
+    for seed in 0..20 {
+        let mut engine = dying_stove("sync", seed);
+        engine.step();
+
+        assert!(!engine.world.agents[0].alive);
+        assert_eq!(engine.world.agents[1].get_var(0), 2);
+    }
+}
+
+#[test]
+fn test_async_mode_sees_earlier_agents() {
+    let mut readings = vec![];
+    for seed in 0..20 {
+        let mut engine = dying_stove("async", seed);
+        engine.step();
+
+        assert!(!engine.world.agents[0].alive);
+        // le champ du poêle éteint a été retiré
+        assert_eq!(engine.fields["heat"].get(Position::new(0, 1)), 0);
+        readings.push(engine.world.agents[1].get_var(0));
+    }
+
+    // selon l'ordre tiré, la sonde passe avant ou après l'extinction du poêle
+    assert!(readings.contains(&2));
+    assert!(readings.contains(&0));
+}
+
+#[test]
+fn test_async_moves_keep_grid_consistent() {
+    let mut engine = engine_from_dsl(r"
+        world 6 6 FFF
+        seed 11
+        mode async
+
+        animal sheep FF5
+
+        agent (void,sheep) (0:6,0:6)
+    ");
+    let count = engine.world.agents.len();
+    for _ in 0..10 {
+        engine.step();
+        for agent in &engine.world.agents {
+            assert_eq!(engine.world.get(agent.pos).unwrap().agent, Some(agent.id));
+        }
+    }
+    let occupied = engine.world.grid.iter().filter(|c| c.agent.is_some()).count();
+    assert_eq!(occupied, count);
+}
+
+#[test]
+fn test_end_status_stops_simulation() {
+    let mut engine = engine_from_dsl(r"
+        world 3 3 FFF
+
+        mineral clock 000
+        var ticks 2 -1
+        status ticks < 1 end
+
+        agent clock (1,1)
+    ");
+    engine.step();
+    assert!(engine.running);
+    engine.step();
+    assert!(!engine.running);
+    // l'agent reste en place, plus rien n'évolue
+    engine.step();
+    assert!(engine.world.agents[0].alive);
+    assert_eq!(engine.world.agents[0].get_var(0), 0);
+}
+
+#[test]
+fn test_trace_status_marks_cell_and_keeps_agent() {
+    let mut engine = engine_from_dsl(r"
+        world 3 3 FFF
+
+        mineral snail 0F0
+        var slime 1
+        status slime < 2 trace
+        status slime > 1 void
+
+        agent snail (1,2)
+    ");
+    engine.step();
+
+    assert!(engine.world.get(Position::new(1, 2)).unwrap().trace);
+    assert!(!engine.world.get(Position::new(1, 1)).unwrap().trace);
+    assert_eq!(agent_at(&engine, 1, 2).status, "snail");
+}
+
+#[test]
+fn test_declared_species_shadows_builtin_status() {
+    use OSCARv2::engine::rules::StatusChange;
+
+    assert_eq!(StatusChange::from_status("void", false), StatusChange::Void);
+    assert_eq!(StatusChange::from_status("end", false), StatusChange::End);
+    assert_eq!(StatusChange::from_status("trace", false), StatusChange::Trace);
+    assert_eq!(StatusChange::from_status("void", true), StatusChange::Become("void".to_string()));
+    assert_eq!(StatusChange::from_status("ash", true), StatusChange::Become("ash".to_string()));
+}
+
+#[test]
+fn test_status_rule_changes_species_in_place() {
+    let mut engine = engine_from_dsl(r"
+        world 3 3 FFF
+
+        mineral tree 0F0
+        var hot 2
+        status hot > 1 fire
+
+        vegetal fire F00
+        var burning 3 -1
+        var flame 2
+
+        agent tree (1,1)
+    ");
+    engine.step();
+
+    let agent = agent_at(&engine, 1, 1);
+    assert_eq!(agent.id, 0);
+    assert_eq!(agent.status, "fire");
+    assert_eq!(agent.species_id, 1);
+    assert_eq!(agent.species, SpeciesKind::Vegetal);
+    assert_eq!(agent.color, 0xFF0000);
+    assert_eq!(agent.vars, vec![3, 2]);
+    assert_eq!(engine.world.agents.len(), 1);
+}
+
+#[test]
+fn test_status_change_inherits_and_reinitialises_vars() {
+    let mut engine = engine_from_dsl(r"
+        world 3 3 FFF
+
+        mineral sheep FF5
+        var grass 26 -1
+        status grass < 26 hungry
+
+        mineral hungry F50
+        var grass grass -1
+        var smell 7
+        status grass < 24 hungry
+
+        agent sheep (0,0)
+    ");
+    engine.step();
+    assert_eq!(engine.world.agents[0].status, "hungry");
+    assert_eq!(engine.world.agents[0].vars, vec![25, 7]);
+
+    // un agent qui rentre dans son propre statut est réinitialisé,
+    // les variables héritées gardent leur valeur
+    engine.world.agents[0].set_var(1, 0);
+    engine.step();
+    engine.step();
+    assert_eq!(engine.world.agents[0].vars, vec![23, 7]);
+}
+
+#[test]
+fn test_wireworld_electron_travels_along_wire() {
+    let mut engine = engine_from_dsl(r"
+        world 6 3 000
+
+        mineral wire FF0
+        var active
+        sensor active electric 1
+        status active < 1 wire
+        status active < 3 head
+        status active > 2 wire
+
+        mineral tail F00
+        status wire
+
+        mineral head 00F
+        var electric 2
+        field electric -1
+        status tail
+
+        agent wire (1,0:6)
+        agent head (1,1)
+        agent tail (1,0)
+    ");
+    let row = |engine: &Engine| -> String {
+        (0..6).map(|col| match agent_at(engine, 1, col).status.as_str() {
+            "wire" => '-',
+            "head" => 'H',
+            "tail" => 't',
+            other => panic!("unexpected status {}", other),
+        }).collect()
+    };
+
+    assert_eq!(row(&engine), "tH----");
+    let expected = ["-tH---", "--tH--", "---tH-", "----tH", "-----t", "------"];
+    for pattern in expected {
+        engine.step();
+        assert_eq!(row(&engine), pattern);
+    }
+}
+
+#[test]
+fn test_birth_creates_child_species_after_rule_pass() {
+    let mut engine = engine_from_dsl(r"
+        world 3 3 FFF
+
+        vegetal grass 0C0
+        var seed 1 -1
+        birth seed < 1 sprout
+
+        vegetal sprout 8F8
+        var age 5 1
+
+        agent grass (1,1)
+    ");
+    engine.step();
+
+    assert_eq!(engine.world.agents.len(), 2);
+    let child = &engine.world.agents[1];
+    assert_eq!(child.status, "sprout");
+    assert_eq!(child.species_id, 1);
+    assert_eq!(child.color, 0x88FF88);
+    // l'enfant n'a pas encore agi : pas de pas de temps appliqué
+    assert_eq!(child.vars, vec![5]);
+    assert!(child.pos.row.abs_diff(1) <= 1 && child.pos.col.abs_diff(1) <= 1);
+    assert_eq!(engine.world.get(child.pos).unwrap().agent, Some(1));
+}
+
+#[test]
+fn test_birth_picks_best_free_neighbour() {
+    let mut engine = engine_from_dsl(r"
+        world 5 5 FFF
+        seed 4
+
+        mineral sun FF0
+        var light 4
+        field light -1
+
+        vegetal plant 0F0
+        var shine
+        sensor shine light 1
+        birth plant
+
+        agent sun (2,0)
+        agent plant (2,2)
+    ");
+    engine.step();
+
+    let child = engine.world.agents.iter().find(|a| a.id == 2).expect("no child");
+    assert_eq!(child.pos.col, 1);
+}
+
+#[test]
+fn test_birth_needs_free_cell_and_living_parent() {
+    // parent entouré : pas de place pour l'enfant
+    let mut engine = engine_from_dsl(r"
+        world 3 3 FFF
+
+        mineral rock 777
+
+        vegetal grass 0C0
+        birth grass
+
+        agent rock (0:3,0:3)
+        agent grass (1,1)
+    ");
+    engine.step();
+    assert_eq!(engine.world.agents.iter().filter(|a| a.alive).count(), 9);
+
+    // parent disparu pendant le même step
+    let mut engine = engine_from_dsl(r"
+        world 3 3 FFF
+
+        vegetal grass 0C0
+        var seed 1 -1
+        birth seed < 1 grass
+        status seed < 1 void
+
+        agent grass (1,1)
+    ");
+    engine.step();
+    assert!(engine.world.agents.iter().all(|a| !a.alive));
+}
+
+#[test]
+fn test_hungry_sheep_absorbs_adjacent_grass() {
+    for mode in ["sync", "async"] {
+        let mut engine = engine_from_dsl(&format!(r"
+            world 4 4 FFF
+            seed 2
+            mode {mode}
+
+            vegetal grass 0C0
+            var grass 5
+            field grass -1
+            status grass < 1 void
+
+            animal hungry F50
+            var grass 10 -1
+            var smell
+            sensor smell grass 1
+
+            agent grass (0,0)
+            agent hungry (1,1)
+        "));
+        // en async, l'herbe traitée avant le mouton ne disparaît qu'au step suivant
+        engine.step();
+        engine.step();
+
+        let grass = &engine.world.agents[0];
+        assert!(!grass.alive);
+        assert_ne!(engine.world.get(Position::new(0, 0)).unwrap().agent, Some(0));
+        // 10 + 5 absorbés - 2 pas de temps
+        assert_eq!(engine.world.agents[1].get_var(0), 13);
+    }
+}
+
+#[test]
+fn test_absorption_requires_sensor_variable_and_other_species() {
+    let mut engine = engine_from_dsl(r"
+        world 4 4 FFF
+
+        vegetal grass 0C0
+        var grass 5
+        field grass -1
+
+        mineral blind 777
+        var grass 1
+
+        mineral nose 777
+        var smell
+        sensor smell grass 1
+
+        vegetal clump 0C0
+        var grass 5
+        field grass -1
+        var smell
+        sensor smell grass 1
+
+        agent grass (1,1)
+        agent blind (0,0)
+        agent nose (0,1)
+        agent clump (3,2) (3,3)
+    ");
+    engine.step();
+    // ni `blind` (sans capteur) ni `nose` (sans variable `grass`) n'absorbent
+    assert_eq!(agent_at(&engine, 1, 1).get_var(0), 5);
+    assert_eq!(agent_at(&engine, 0, 0).get_var(0), 1);
+    // deux agents de la même espèce ne s'absorbent pas
+    assert_eq!(agent_at(&engine, 3, 2).get_var(0), 5);
+    assert_eq!(agent_at(&engine, 3, 3).get_var(0), 5);
+}
+
+#[test]
+fn test_sync_absorption_reads_start_of_phase() {
+    for seed in 0..10 {
+        let mut engine = engine_from_dsl(&format!(r"
+            world 5 5 FFF
+            seed {}
+
+            vegetal grass 0C0
+            var grass 7
+            field grass -1
+
+            mineral goat 777
+            var grass 0
+            field grass -1
+            var smell
+            sensor smell grass 1
+
+            mineral wolf 000
+            var grass 0
+            var smell
+            sensor smell grass 1
+
+            agent grass (2,0) (0,4)
+            agent goat (2,1) (0,3) (1,4)
+            agent wolf (2,2)
+        ", seed));
+        engine.step();
+
+        // la chèvre mange l'herbe, mais le loup ne lui prend que ce qu'elle
+        // avait au début de la phase
+        assert_eq!(agent_at(&engine, 2, 0).get_var(0), 0);
+        assert_eq!(agent_at(&engine, 2, 1).get_var(0), 7);
+        assert_eq!(agent_at(&engine, 2, 2).get_var(0), 0);
+
+        // deux chèvres autour de la même herbe se la partagent
+        assert_eq!(agent_at(&engine, 0, 4).get_var(0), 0);
+        let mut shares = [agent_at(&engine, 0, 3).get_var(0), agent_at(&engine, 1, 4).get_var(0)];
+        shares.sort();
+        assert_eq!(shares, [3, 4]);
+    }
+}
+
+#[test]
+fn test_rules_read_the_named_variable() {
+    let mut engine = engine_from_dsl(r"
+        world 3 3 FFF
+
+        vegetal grass 0C0
+        var grass 5
+        var seed 2 -1
+        birth seed < 1 grass
+
+        agent grass (1,1)
+    ");
+    engine.step();
+    assert_eq!(engine.world.agents.len(), 1);
+    engine.step();
+    // `seed` atteint 0 au deuxième step, `grass` vaut toujours 5
+    assert_eq!(engine.world.agents.len(), 2);
+}
+
+#[test]
+fn test_timestep_applies_after_sensing() {
+    for mode in ["sync", "async"] {
+        let mut engine = engine_from_dsl(&format!(r"
+            world 3 1 FFF
+            mode {mode}
+
+            mineral stove F00
+            var heat 3
+            field heat -1
+
+            mineral probe 0F0
+            var warm 0 10
+            sensor warm heat 1
+
+            agent stove (0,0)
+            agent probe (0,2)
+        "));
+        engine.step();
+        // lecture du champ (1) puis pas de temps (+10)
+        assert_eq!(engine.world.agents[1].get_var(0), 11);
+        engine.step();
+        assert_eq!(engine.world.agents[1].get_var(0), 11);
+    }
+}
+
+#[test]
+fn test_countdown_triggers_status_on_expected_step() {
+    let mut engine = engine_from_dsl(r"
+        world 3 3 000
+
+        animal proto FFA
+        var cycle 3 -1
+        status cycle < 1 étoile
+
+        animal étoile FF0
+        var durée 20 -1
+
+        agent proto (1,1)
+    ");
+    for _ in 0..2 {
+        engine.step();
+        assert_eq!(engine.world.agents[0].status, "proto");
+    }
+    engine.step();
+    // la règle lit `cycle` après son pas de temps : 3 - 3 = 0
+    assert_eq!(engine.world.agents[0].status, "étoile");
+    assert_eq!(engine.world.agents[0].vars, vec![20]);
+    engine.step();
+    assert_eq!(engine.world.agents[0].vars, vec![19]);
+}
+
+#[test]
+fn test_sensor_ignores_own_emission() {
+    let sensor = SensorDef {
+        name: "warm".to_string(),
+        target_var: 0,
+        terms: vec![
+            SensorTerm { field_name: "heat".to_string(), weight: 1.0 },
+            SensorTerm { field_name: "cold".to_string(), weight: -1.0 },
+        ],
+    };
+    let mut fields = std::collections::HashMap::new();
+    for (name, value) in [("heat", 5), ("cold", 2)] {
+        let mut field = OSCARv2::engine::field::Field::new(FieldDef { name: name.to_string(), step: 1, ..Default::default() }, 1, 1);
+        field.add_source(Position::new(0, 0), value);
+        fields.insert(name.to_string(), field);
+    }
+
+    assert_eq!(sensor.evaluate(&fields, 0, |_| 0), 3);
+    assert_eq!(sensor.evaluate(&fields, 0, |name| if name == "heat" { 4 } else { 0 }), -1);
+    let own = |name: &str| match name { "cold" => 2, "light" => 9, _ => 0 };
+    assert_eq!(sensor.evaluate(&fields, 0, own), 5);
+
+    // un émetteur seul ne se perçoit pas lui-même
+    let mut engine = engine_from_dsl(r"
+        world 3 3 FFF
+
+        mineral lamp FF0
+        var light 5
+        field light -1
+        var glow
+        sensor glow light 1
+
+        agent lamp (1,1) (1,2)
+    ");
+    engine.step();
+    assert_eq!(agent_at(&engine, 1, 1).get_var(1), 4);
+    assert_eq!(agent_at(&engine, 1, 2).get_var(1), 4);
+}
+
+#[test]
+fn test_life_blinker_oscillates() {
+    let mut engine = engine_from_dsl(r"
+        world 5 5 FFF
+
+        mineral dead FFF
+        var neighbor
+        status neighbor < 3 dead
+        status neighbor < 4 live
+        status neighbor > 3 dead
+        sensor neighbor life 1
+
+        mineral live 000
+        var life 2
+        var neighbor
+        status neighbor < 2 dead
+        status neighbor < 4 live
+        status neighbor > 3 dead
+        field life -1
+        sensor neighbor life 1
+
+        agent dead (0:5,0:5)
+        agent live (2,1:4)
+    ");
+    let live_cells = |engine: &Engine| -> Vec<(usize, usize)> {
+        let mut cells: Vec<_> = engine.world.agents.iter()
+            .filter(|a| a.alive && a.status == "live")
+            .map(|a| (a.pos.row, a.pos.col))
+            .collect();
+        cells.sort();
+        cells
+    };
+
+    let horizontal = vec![(2, 1), (2, 2), (2, 3)];
+    let vertical = vec![(1, 2), (2, 2), (3, 2)];
+    assert_eq!(live_cells(&engine), horizontal);
+    for _ in 0..3 {
+        engine.step();
+        assert_eq!(live_cells(&engine), vertical);
+        engine.step();
+        assert_eq!(live_cells(&engine), horizontal);
+    }
+}
+
+/// Charge un monde fourni avec une graine fixe : les motifs aléatoires
+/// (`agent (void,tree,tree,tree) ...`) sont alors reproductibles
+fn engine_from_world(file_path: &str) -> Engine {
+    let content = std::fs::read_to_string(file_path).unwrap();
+    engine_from_dsl(&format!("seed 1\n{}", content))
+}
+
+fn cells_with_status(engine: &Engine, status: &str) -> Vec<(usize, usize)> {
+    let mut cells: Vec<_> = engine.world.agents.iter()
+        .filter(|a| a.alive && a.status == status)
+        .map(|a| (a.pos.row, a.pos.col))
+        .collect();
+    cells.sort();
+    cells
+}
+
+#[test]
+fn test_bundled_wireworld_electrons_move() {
+    let mut engine = engine_from_world("worlds/oscar2.txt");
+    assert_eq!(cells_with_status(&engine, "head"), vec![(8, 0), (8, 8)]);
+
+    // sans queue, l'électron central part des deux côtés
+    for step in 1..=3 {
+        engine.step();
+        assert_eq!(cells_with_status(&engine, "head"), vec![(8, step), (8, 8 - step), (8, 8 + step)]);
+        let mut tails = vec![(8, step - 1), (8, 8 + 1 - step), (8, 7 + step)];
+        tails.sort();
+        tails.dedup();
+        assert_eq!(cells_with_status(&engine, "tail"), tails);
+    }
+}
+
+#[test]
+fn test_bundled_pentadecathlon_has_period_15() {
+    let mut engine = engine_from_world("worlds/oscar3.txt");
+    let initial = cells_with_status(&engine, "live");
+    assert_eq!(initial.len(), 12);
+
+    for _ in 0..14 {
+        engine.step();
+        assert_ne!(cells_with_status(&engine, "live"), initial);
+    }
+    engine.step();
+    assert_eq!(cells_with_status(&engine, "live"), initial);
+}
+
+#[test]
+fn test_bundled_forest_fire_spreads() {
+    let mut engine = engine_from_world("worlds/oscar1.txt");
+    let trees = cells_with_status(&engine, "tree").len();
+    let fires = cells_with_status(&engine, "fire").len();
+    assert!(fires > 0);
+
+    for _ in 0..5 {
+        engine.step();
+    }
+    assert!(cells_with_status(&engine, "ash").len() >= fires);
+    assert!(cells_with_status(&engine, "tree").len() < trees);
+}
+
+#[test]
+fn test_persistent_field_outlives_its_emitter() {
+    let world = |kind: &str| format!("
+        world 5 5 FFF
+        mineral scent 0F0
+        var smell 16
+        field smell -16 {}
+        status void
+        mineral nose 000
+        var smelled
+        sensor smelled smell 1
+        agent scent (2,2)
+        agent nose (2,3)", kind);
+
+    // la source disparaît dès le premier pas : il ne reste que sa trace
+    let mut engine = engine_from_dsl(&world("persist 0.5 0.25"));
+    engine.step();
+    assert!(!engine.world.agents[0].alive);
+    assert_eq!(agent_at(&engine, 2, 3).get_var(0), 0);
+    engine.step();
+    assert_eq!(agent_at(&engine, 2, 3).get_var(0), 1);
+    for _ in 0..20 {
+        engine.step();
+    }
+    assert_eq!(agent_at(&engine, 2, 3).get_var(0), 0);
+
+    let mut engine = engine_from_dsl(&world(""));
+    engine.step();
+    engine.step();
+    assert_eq!(agent_at(&engine, 2, 3).get_var(0), 0);
+}
+
+/// Champs recalculés de zéro à partir d'un état du monde
+fn fields_from_scratch(engine: &Engine, agents: &[Agent]) -> Vec<(String, Vec<i32>)> {
+    let mut names: Vec<_> = engine.fields.keys().cloned().collect();
+    names.sort();
+    names.into_iter().map(|name| {
+        let (rows, cols) = (engine.world.rows, engine.world.cols);
+        let mut field = OSCARv2::engine::field::Field::new(engine.fields[&name].def.clone(), rows, cols);
+        let alive = || agents.iter().filter(|a| a.alive);
+        for agent in alive() {
+            if engine.species_defs[agent.species_id].blocks.contains(&name) {
+                field.block(agent.pos);
+            }
+        }
+        for agent in alive() {
+            let spec = &engine.species_defs[agent.species_id];
+            if spec.field_defs.iter().any(|f| f.name == name) {
+                field.add_source(agent.pos, agent.get_var(spec.var_index(&name).unwrap()));
+            }
+        }
+        (name, field.values)
+    }).collect()
+}
+
+#[test]
+fn test_incremental_fields_match_full_recompute() {
+    let mut engine = engine_from_dsl("
+        world 12 12 FFF
+        seed 3
+        mineral lamp FF0
+        var light 9
+        field light -1 euclid
+        mineral rock 777
+        var solid 6 -1
+        status solid < 1 void
+        block scent
+        block light
+        animal ant 000
+        var scent 3
+        field scent -1
+        var smell
+        sensor smell scent 1
+        birth smell > 3 ant
+        status smell > 7 void
+        agent lamp (0,0)
+        agent (void,void,void,ant) (0:12,0:12)
+        agent rock (3:9:2,6)")
+        // aucun capteur ne lit `light` : on le fait calculer comme pour l'affichage
+        .with_rendered_fields(&["light"]);
+    assert_eq!(engine.fields.len(), 2);
+
+    let mut moved = false;
+    for _ in 0..12 {
+        let before = engine.world.agents.clone();
+        engine.step();
+        moved |= before.iter().zip(&engine.world.agents).any(|(a, b)| a.alive && a.pos != b.pos);
+
+        // en mode sync, les champs reflètent les sources du début du pas
+        let mut incremental: Vec<_> = engine.fields.iter()
+            .map(|(name, f)| (name.clone(), f.values.clone()))
+            .collect();
+        incremental.sort();
+        assert_eq!(incremental, fields_from_scratch(&engine, &before));
+    }
+    assert!(moved);
+    let rocks: Vec<_> = engine.world.agents.iter().filter(|a| a.status == "rock").collect();
+    assert_eq!(rocks.len(), 3);
+    assert!(rocks.iter().all(|a| !a.alive));
+}
+
+#[test]
+fn test_only_read_fields_are_computed() {
+    let world = "
+        world 5 5 FFF
+        mineral lamp FF0
+        var light 3
+        field light -1
+        var heat 2
+        field heat -1
+        mineral plant 0F0
+        var warm
+        sensor warm heat 1
+        agent lamp (2,2)
+        agent plant (2,3)";
+
+    let mut engine = engine_from_dsl(world);
+    assert!(engine.fields.contains_key("heat"));
+    assert!(!engine.fields.contains_key("light"));
+    engine.step();
+    assert_eq!(agent_at(&engine, 2, 3).get_var(0), 1);
+
+    // un champ affiché est calculé même si aucun capteur ne le lit
+    let mut engine = engine_from_dsl(world).with_rendered_fields(&["light", "smoke"]);
+    assert!(!engine.fields.contains_key("smoke"));
+    engine.step();
+    assert_eq!(engine.fields["light"].get(Position::new(2, 4)), 1);
+}
+
+#[test]
+fn test_species_vars_by_name() {
+    let spec = SpeciesDef {
+        species_id: 0,
+        name: "grass".to_string(),
+        kind: SpeciesKind::Vegetal,
+        color: 0x00CC00,
+        vars: ["grass", "seed"].iter().map(|name| VarSpec {
+            name: name.to_string(),
+            init: VarInit::Value(0),
+            timestep: 0,
+        }).collect(),
+        status_rules: vec![],
+        birth_rules: vec![],
+        field_defs: vec![],
+        sensors: vec![],
+        blocks: vec![],
+    };
+    let mut agent = Agent::new(0, Position::new(0, 0), SpeciesKind::Vegetal, 0, "grass", 0x00CC00, 2);
+    agent.vars = vec![5, 3];
+
+    assert_eq!(spec.get_var_by_name(&agent, "seed"), Some(3));
+    assert_eq!(spec.get_var_by_name(&agent, "smell"), None);
+
+    spec.set_var_by_name(&mut agent, "grass", 1).unwrap();
+    assert_eq!(agent.vars, vec![1, 3]);
+    let err = spec.set_var_by_name(&mut agent, "smell", 1).unwrap_err();
+    assert!(format!("{}", err).contains("Variable 'smell' not declared in species 'grass'"));
+}
@@ -81,6 +81,74 @@ fn test_parse_field_without_species() {
     assert!(error_msg.contains("field outside of species"));
 }
 
+#[test]
+fn test_parse_birth_without_species() {
+    let input = "birth seed < 1 grass";  // birth outside of species definition
+    let result = parse_file(input);
+    assert!(result.is_err());
+
+    let error_msg = format!("{}", result.unwrap_err());
+    assert!(error_msg.contains("birth outside of species"));
+}
+
+#[test]
+fn test_parse_field_without_step() {
+    let input = r"
+        mineral fire F00
+        var flame 2
+        field flame";
+    let result = parse_file(input);
+    assert!(result.is_err());
+
+    let error_msg = format!("{}", result.unwrap_err());
+    assert!(error_msg.contains("Expected integer field step at line 4"));
+}
+
+#[test]
+fn test_parse_sensor_without_weight() {
+    let input = r"
+        mineral tree 0F0
+        var hot
+        sensor hot flame";
+    let result = parse_file(input);
+    assert!(result.is_err());
+
+    let error_msg = format!("{}", result.unwrap_err());
+    assert!(error_msg.contains("Expected sensor weight at line 4"));
+}
+
+#[test]
+fn test_parse_invalid_seed() {
+    let result = parse_file("world 4 4 FFF\nseed fast");
+    let error_msg = format!("{}", result.unwrap_err());
+    assert!(error_msg.contains("Expected integer seed at line 2"));
+
+    // le tokenizer lit `-3` comme un nombre négatif
+    let result = parse_file("seed -3");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_parse_invalid_update_mode() {
+    let result = parse_file("world 4 4 FFF\nmode parallel");
+    let error_msg = format!("{}", result.unwrap_err());
+    assert!(error_msg.contains("Expected update mode 'sync' or 'async' at line 2"));
+}
+
+#[test]
+fn test_parse_invalid_field_decay() {
+    let result = parse_file("mineral lamp FF0\nvar light 3\nfield light -1 euclid cubic");
+    let error_msg = format!("{}", result.unwrap_err());
+    assert!(error_msg.contains("Unknown field option 'cubic' at line 3"));
+}
+
+#[test]
+fn test_parse_persist_without_rates() {
+    let result = parse_file("animal ant 000\nvar pheromone 3\nfield pheromone -1 persist 0.2");
+    let error_msg = format!("{}", result.unwrap_err());
+    assert!(error_msg.contains("Expected diffusion and evaporation rates after persist at line 3"));
+}
+
 #[test]
 fn test_parse_var_without_name() {
     let input = r"
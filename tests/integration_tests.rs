@@ -1,5 +1,5 @@
 use OSCARv2::dsl::parser::parse_file;
-use OSCARv2::dsl::ast::SpeciesKind;
+use OSCARv2::dsl::ast::{CompareOp, SpeciesKind};
 use std::fs;
 
 #[test]
@@ -225,6 +225,14 @@ fn test_parse_oscar5_segregation() {
     assert!(matches!(blue_species.kind, SpeciesKind::Animal));
     assert_eq!(blue_species.color, "00F");
     
+    // Check fields and merged sensors
+    assert_eq!(red_species.fields.len(), 1);
+    assert_eq!(red_species.fields[0].name, "red");
+    assert_eq!(red_species.fields[0].step, -1);
+    assert_eq!(red_species.sensors.len(), 1);
+    assert_eq!(red_species.sensors[0].name, "comfort");
+    assert_eq!(red_species.sensors[0].fields.len(), 2);
+
     // Check agents
     assert_eq!(result.agents.len(), 1);
     let mixed_agent = &result.agents[0];
@@ -281,6 +289,11 @@ fn test_parse_oscar6_sugarscape() {
     let sheep_agent = &result.agents[1];
     assert_eq!(sheep_agent.species_pattern, "sheep");
     assert_eq!(sheep_agent.positions.len(), 2); // 2 sheep lines
+
+    // Germination de l'herbe
+    assert_eq!(grass_species.births.len(), 1);
+    assert_eq!(grass_species.births[0].variable.as_deref(), Some("seed"));
+    assert_eq!(grass_species.births[0].child_status, "grass");
 }
 
 #[test] 
@@ -326,6 +339,32 @@ fn test_parse_oscarc_grid_transformation() {
     assert_eq!(result.agents.len(), 3);
 }
 
+#[test]
+fn test_parse_status_conditions_in_bundled_worlds() {
+    let content = fs::read_to_string("worlds/oscar1.txt")
+        .expect("Failed to read oscar1.txt");
+    let result = parse_file(&content).expect("Failed to parse oscar1.txt");
+
+    let tree = result.species.iter().find(|s| s.name == "tree").unwrap();
+    assert_eq!(tree.statuses[0].variable.as_deref(), Some("hot"));
+    assert_eq!(tree.statuses[0].op, Some(CompareOp::Less));
+    assert_eq!(tree.statuses[0].threshold, Some(2));
+    assert_eq!(tree.statuses[0].new_status, "tree");
+    assert_eq!(tree.statuses[1].op, Some(CompareOp::Greater));
+    assert_eq!(tree.statuses[1].new_status, "fire");
+
+    let content = fs::read_to_string("worlds/oscarc.txt")
+        .expect("Failed to read oscarc.txt");
+    let result = parse_file(&content).expect("Failed to parse oscarc.txt");
+
+    let void = result.species.iter().find(|s| s.name == "void").unwrap();
+    assert_eq!(void.statuses[0].op, Some(CompareOp::Equal));
+    assert_eq!(void.statuses[0].threshold, Some(3));
+    assert_eq!(void.statuses[0].new_status, "dark_blue");
+    assert_eq!(void.statuses[1].op, Some(CompareOp::NotEqual));
+    assert_eq!(void.statuses[1].new_status, "light_blue");
+}
+
 #[test]
 fn test_parsing_consistency() {
     // Test that all example files can be parsed without errors
@@ -1,9 +1,10 @@
 use OSCARv2::model::builder::WorldBuilder;
 use OSCARv2::model::agent::SpeciesKind;
 use OSCARv2::dsl::ast::{
-    ConfigAst, World as WorldDSL, Species,
+    AgentDef, ConfigAst, World as WorldDSL, Species,
     VarDef, Value, SpeciesKind as DslSpeciesKind
 };
+use OSCARv2::model::position::Position;
 
 #[test]
 fn test_world_builder_from_config_empty() {
@@ -11,6 +12,8 @@ fn test_world_builder_from_config_empty() {
         world: None,
         species: vec![],
         agents: vec![],
+        seed: None,
+        mode: None,
     };
     
     let result = WorldBuilder::from_config(&config);
@@ -29,6 +32,8 @@ fn test_world_builder_from_config_world_only() {
         }),
         species: vec![],
         agents: vec![],
+        seed: None,
+        mode: None,
     };
     
     let result = WorldBuilder::from_config(&config);
@@ -65,6 +70,7 @@ fn test_world_builder_from_config_with_species() {
                 births: vec![],
                 fields: vec![],
                 sensors: vec![],
+                blocks: vec![],
             },
             Species {
                 kind: DslSpeciesKind::Vegetal,
@@ -75,9 +81,12 @@ fn test_world_builder_from_config_with_species() {
                 births: vec![],
                 fields: vec![],
                 sensors: vec![],
+                blocks: vec![],
             },
         ],
         agents: vec![],
+        seed: None,
+        mode: None,
     };
     
     let result = WorldBuilder::from_config(&config);
@@ -97,6 +106,7 @@ fn test_world_builder_from_config_with_species() {
     assert_eq!(wolf.kind, SpeciesKind::Animal);
     assert_eq!(wolf.var_names.len(), 1);
     assert_eq!(wolf.var_names[0], "hunger");
+    assert_eq!(wolf.init_values, vec![50]);
     
     let tree = &builder.species_map["Tree"];
     assert_eq!(tree.kind, SpeciesKind::Vegetal);
@@ -113,6 +123,8 @@ fn test_world_builder_edge_case_zero_size_world() {
         }),
         species: vec![],
         agents: vec![],
+        seed: None,
+        mode: None,
     };
     
     let result = WorldBuilder::from_config(&config);
@@ -148,6 +160,7 @@ fn test_world_builder_multiple_species_kinds() {
                 births: vec![],
                 fields: vec![],
                 sensors: vec![],
+                blocks: vec![],
             },
             Species {
                 kind: DslSpeciesKind::Vegetal,
@@ -164,6 +177,7 @@ fn test_world_builder_multiple_species_kinds() {
                 births: vec![],
                 fields: vec![],
                 sensors: vec![],
+                blocks: vec![],
             },
             Species {
                 kind: DslSpeciesKind::Animal,
@@ -180,9 +194,12 @@ fn test_world_builder_multiple_species_kinds() {
                 births: vec![],
                 fields: vec![],
                 sensors: vec![],
+                blocks: vec![],
             },
         ],
         agents: vec![],
+        seed: None,
+        mode: None,
     };
     
     let result = WorldBuilder::from_config(&config);
@@ -216,9 +233,12 @@ fn test_world_builder_edge_case_no_world_definition() {
                 births: vec![],
                 fields: vec![],
                 sensors: vec![],
+                blocks: vec![],
             },
         ],
         agents: vec![],
+        seed: None,
+        mode: None,
     };
     
     let result = WorldBuilder::from_config(&config);
@@ -244,9 +264,12 @@ fn test_world_builder_large_world() {
                 births: vec![],
                 fields: vec![],
                 sensors: vec![],
+                blocks: vec![],
             },
         ],
         agents: vec![],
+        seed: None,
+        mode: None,
     };
     
     let result = WorldBuilder::from_config(&config);
@@ -258,3 +281,211 @@ fn test_world_builder_large_world() {
     assert_eq!(builder.world.grid.len(), 20_000);
     assert_eq!(builder.species_map.len(), 1);
 }
+
+#[test]
+fn test_world_builder_species_id_follows_declaration_order() {
+    let config = ConfigAst {
+        world: Some(WorldDSL {
+            rows: 4,
+            cols: 4,
+            color: "white".to_string(),
+        }),
+        species: vec![
+            Species {
+                kind: DslSpeciesKind::Mineral,
+                name: "wire".to_string(),
+                color: "yellow".to_string(),
+                vars: vec![],
+                statuses: vec![],
+                births: vec![],
+                fields: vec![],
+                sensors: vec![],
+                blocks: vec![],
+            },
+            Species {
+                kind: DslSpeciesKind::Mineral,
+                name: "head".to_string(),
+                color: "blue".to_string(),
+                vars: vec![],
+                statuses: vec![],
+                births: vec![],
+                fields: vec![],
+                sensors: vec![],
+                blocks: vec![],
+            },
+        ],
+        agents: vec![
+            AgentDef {
+                species_pattern: "wire".to_string(),
+                positions: vec!["(1,1)".to_string(), "(1,2)".to_string(), "(1,3)".to_string()],
+            },
+            AgentDef {
+                species_pattern: "head".to_string(),
+                positions: vec!["(1,2)".to_string()],
+            },
+        ],
+        seed: None,
+        mode: None,
+    };
+
+    let mut builder = WorldBuilder::from_config(&config).unwrap();
+    assert_eq!(builder.species_map["wire"].species_id, 0);
+    assert_eq!(builder.species_map["head"].species_id, 1);
+
+    builder.place_agents(&config).unwrap();
+    let (world, agents) = builder.build();
+
+    // La tête remplace le fil déjà placé sur sa case
+    let head_id = world.get(Position::new(1, 2)).unwrap().agent.unwrap();
+    assert_eq!(agents[head_id].species_id, 1);
+    assert_eq!(agents[head_id].status, "head");
+    assert_eq!(agents.iter().filter(|a| a.alive).count(), 3);
+    assert_eq!(agents.iter().filter(|a| !a.alive).count(), 1);
+}
+
+/// Monde 16x16 avec une seule espèce `live`
+fn life_config(positions: &[&str]) -> ConfigAst {
+    ConfigAst {
+        world: Some(WorldDSL { rows: 16, cols: 16, color: "white".to_string() }),
+        species: vec![Species {
+            kind: DslSpeciesKind::Mineral,
+            name: "live".to_string(),
+            color: "black".to_string(),
+            vars: vec![],
+            statuses: vec![],
+            births: vec![],
+            fields: vec![],
+            sensors: vec![],
+            blocks: vec![],
+        }],
+        agents: vec![AgentDef {
+            species_pattern: "live".to_string(),
+            positions: positions.iter().map(|p| p.to_string()).collect(),
+        }],
+        seed: None,
+        mode: None,
+    }
+}
+
+fn placed_positions(config: &ConfigAst) -> Vec<(usize, usize)> {
+    let mut builder = WorldBuilder::from_config(config).unwrap();
+    builder.place_agents(config).unwrap();
+    let (_, agents) = builder.build();
+    let mut positions: Vec<_> = agents.iter().map(|a| (a.pos.row, a.pos.col)).collect();
+    positions.sort();
+    positions
+}
+
+#[test]
+fn test_world_builder_range_end_is_exclusive() {
+    let config = life_config(&["(0:16,0:16)"]);
+    assert_eq!(placed_positions(&config).len(), 256);
+
+    let config = life_config(&["(8,3:5)"]);
+    assert_eq!(placed_positions(&config), vec![(8, 3), (8, 4)]);
+}
+
+#[test]
+fn test_world_builder_stepped_range() {
+    // pentadecathlon de oscar3.txt
+    let config = life_config(&["(7:10:2,5:11:5)"]);
+    assert_eq!(placed_positions(&config), vec![(7, 5), (7, 10), (9, 5), (9, 10)]);
+
+    let config = life_config(&["(0:16:8,1)"]);
+    assert_eq!(placed_positions(&config), vec![(0, 1), (8, 1)]);
+}
+
+#[test]
+fn test_world_builder_rejects_invalid_ranges() {
+    for position in ["(0:17,0)", "(16,0)", "(0:4:0,0)", "(4:4,0)", "(0:1:2:3,0)", "(a:4,0)", "(1,2,3)"] {
+        let config = life_config(&[position]);
+        let mut builder = WorldBuilder::from_config(&config).unwrap();
+        assert!(builder.place_agents(&config).is_err(), "{} should be rejected", position);
+    }
+
+    // un indice maximal ne doit pas déborder en calculant la fin du range
+    let config = life_config(&["(18446744073709551615,0)"]);
+    let mut builder = WorldBuilder::from_config(&config).unwrap();
+    let err = builder.place_agents(&config).unwrap_err();
+    assert!(err.to_string().contains("hors de la grille"), "{}", err);
+}
+
+fn mineral(name: &str) -> Species {
+    Species {
+        kind: DslSpeciesKind::Mineral,
+        name: name.to_string(),
+        color: "green".to_string(),
+        vars: vec![],
+        statuses: vec![],
+        births: vec![],
+        fields: vec![],
+        sensors: vec![],
+        blocks: vec![],
+    }
+}
+
+fn pattern_config(species: &[&str], pattern: &str) -> ConfigAst {
+    ConfigAst {
+        world: Some(WorldDSL { rows: 32, cols: 32, color: "white".to_string() }),
+        species: species.iter().map(|name| mineral(name)).collect(),
+        agents: vec![AgentDef {
+            species_pattern: pattern.to_string(),
+            positions: vec!["(0:32,0:32)".to_string()],
+        }],
+        seed: None,
+        mode: None,
+    }
+}
+
+fn seeded_placement(config: &ConfigAst, seed: u64) -> Vec<(usize, usize, usize)> {
+    let mut builder = WorldBuilder::from_config(config).unwrap().with_seed(seed);
+    builder.place_agents(config).unwrap();
+    let (_, agents) = builder.build();
+    agents.iter().map(|a| (a.pos.row, a.pos.col, a.species_id)).collect()
+}
+
+#[test]
+fn test_world_builder_weighted_pattern() {
+    let config = pattern_config(&["tree"], "(void,tree,tree,tree)");
+    let placed = seeded_placement(&config, 7);
+
+    // 3/4 des 1024 cases attendues, avec une marge large
+    assert!((700..840).contains(&placed.len()), "{} trees placed", placed.len());
+    assert!(placed.iter().all(|&(_, _, species_id)| species_id == 0));
+}
+
+#[test]
+fn test_world_builder_mixed_pattern() {
+    let config = pattern_config(&["red", "blue"], "(void,red,blue)");
+    let placed = seeded_placement(&config, 7);
+
+    let red = placed.iter().filter(|p| p.2 == 0).count();
+    let blue = placed.iter().filter(|p| p.2 == 1).count();
+    assert!((280..400).contains(&red), "{} red placed", red);
+    assert!((280..400).contains(&blue), "{} blue placed", blue);
+}
+
+#[test]
+fn test_world_builder_pattern_is_reproducible_under_seed() {
+    let config = pattern_config(&["red", "blue"], "(void,red,blue)");
+
+    assert_eq!(seeded_placement(&config, 42), seeded_placement(&config, 42));
+    assert_ne!(seeded_placement(&config, 42), seeded_placement(&config, 43));
+}
+
+#[test]
+fn test_world_builder_pattern_declared_void_and_unknown_species() {
+    // une espèce nommée `void` est placée comme les autres
+    let config = pattern_config(&["void"], "void");
+    assert_eq!(seeded_placement(&config, 1).len(), 1024);
+
+    let config = pattern_config(&["tree"], "(void,oak)");
+    let mut builder = WorldBuilder::from_config(&config).unwrap();
+    assert!(builder.place_agents(&config).is_err());
+}
+
+#[test]
+fn test_world_builder_undeclared_void_leaves_cells_empty() {
+    let config = pattern_config(&["tree"], "void");
+    assert!(seeded_placement(&config, 1).is_empty());
+}
@@ -1,5 +1,5 @@
 use OSCARv2::dsl::parser::parse_file;
-use OSCARv2::dsl::ast::{SpeciesKind, Value};
+use OSCARv2::dsl::ast::{CompareOp, Decay, FieldKind, Metric, SpeciesKind, UpdateMode, Value};
 
 #[test]
 fn test_parse_world_command() {
@@ -86,6 +86,48 @@ fn test_parse_species_with_var() {
     assert_eq!(species.vars[1].timestep, 0);
 }
 
+#[test]
+fn test_parse_var_with_init_and_timestep() {
+    let input = r"
+        vegetal grass 0C0
+        var grass 40 -1
+        var seed 3
+        var age 0 1";
+
+    let result = parse_file(input).expect("Failed to parse var with values");
+
+    let vars = &result.species[0].vars;
+    assert_eq!(vars.len(), 3);
+
+    assert_eq!(vars[0].name, "grass");
+    assert!(matches!(vars[0].init_value, Value::Int(40)));
+    assert_eq!(vars[0].timestep, -1);
+
+    assert_eq!(vars[1].name, "seed");
+    assert!(matches!(vars[1].init_value, Value::Int(3)));
+    assert_eq!(vars[1].timestep, 0);
+
+    assert_eq!(vars[2].name, "age");
+    assert!(matches!(vars[2].init_value, Value::Int(0)));
+    assert_eq!(vars[2].timestep, 1);
+}
+
+#[test]
+fn test_parse_var_with_inherited_init() {
+    let input = r"
+        animal hungry F50
+        var grass grass -1
+        var smell";
+
+    let result = parse_file(input).expect("Failed to parse inherited var");
+
+    let vars = &result.species[0].vars;
+    assert_eq!(vars.len(), 2);
+    assert!(matches!(&vars[0].init_value, Value::Str(s) if s == "grass"));
+    assert_eq!(vars[0].timestep, -1);
+    assert!(matches!(vars[1].init_value, Value::Int(0)));
+}
+
 #[test]
 fn test_parse_species_with_status() {
     let input = r"
@@ -101,10 +143,224 @@ fn test_parse_species_with_status() {
     
     assert_eq!(species.statuses[0].new_status, "void");
     assert!(species.statuses[0].variable.is_none());
-    assert!(species.statuses[0].less_than.is_none());
+    assert!(species.statuses[0].op.is_none());
     assert!(species.statuses[0].threshold.is_none());
 }
 
+#[test]
+fn test_parse_conditional_status() {
+    let input = r"
+        mineral tree 0F0
+        var hot
+        status hot < 2 tree
+        status hot > 1 fire
+        status hot == 3 ash
+        status hot != 0 smoke";
+
+    let result = parse_file(input).expect("Failed to parse conditional status");
+
+    let statuses = &result.species[0].statuses;
+    assert_eq!(statuses.len(), 4);
+
+    let expected = [
+        (CompareOp::Less, 2, "tree"),
+        (CompareOp::Greater, 1, "fire"),
+        (CompareOp::Equal, 3, "ash"),
+        (CompareOp::NotEqual, 0, "smoke"),
+    ];
+    for (rule, (op, threshold, new_status)) in statuses.iter().zip(expected) {
+        assert_eq!(rule.variable.as_deref(), Some("hot"));
+        assert_eq!(rule.op, Some(op));
+        assert_eq!(rule.threshold, Some(threshold));
+        assert_eq!(rule.new_status, new_status);
+    }
+}
+
+#[test]
+fn test_parse_conditional_status_without_threshold() {
+    let input = r"
+        mineral tree 0F0
+        var hot
+        status hot < fire";
+
+    let err = parse_file(input).unwrap_err();
+    assert!(format!("{}", err).contains("Expected integer threshold in condition at line 4"));
+}
+
+#[test]
+fn test_parse_birth_rules() {
+    let input = r"
+        vegetal grass 0C0
+        var seed 3 -1
+        birth seed < 1 grass
+        birth grass";
+
+    let result = parse_file(input).expect("Failed to parse birth rules");
+
+    let births = &result.species[0].births;
+    assert_eq!(births.len(), 2);
+    assert_eq!(births[0].variable.as_deref(), Some("seed"));
+    assert_eq!(births[0].op, Some(CompareOp::Less));
+    assert_eq!(births[0].threshold, Some(1));
+    assert_eq!(births[0].child_status, "grass");
+    assert!(births[1].variable.is_none());
+    assert!(births[1].op.is_none());
+    assert_eq!(births[1].child_status, "grass");
+}
+
+#[test]
+fn test_parse_birth_child_declared_later() {
+    let input = r"
+        vegetal tree 0F0
+        birth seed
+        vegetal seed 060";
+
+    let result = parse_file(input).expect("Failed to parse forward birth reference");
+    assert_eq!(result.species[0].births[0].child_status, "seed");
+}
+
+#[test]
+fn test_parse_birth_unknown_child_status() {
+    let input = r"
+        vegetal grass 0C0
+        var seed 3 -1
+        birth seed < 1 gras";
+
+    let err = parse_file(input).unwrap_err();
+    assert!(format!("{}", err).contains("Unknown child status 'gras' in birth rule at line 4"));
+}
+
+#[test]
+fn test_parse_field_declaration() {
+    let input = r"
+        mineral fire F00
+        var flame 2
+        field flame -1";
+
+    let result = parse_file(input).expect("Failed to parse field");
+
+    let fields = &result.species[0].fields;
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].name, "flame");
+    assert_eq!(fields[0].step, -1);
+}
+
+#[test]
+fn test_parse_sensor_declarations() {
+    let input = r"
+        animal red F00
+        var comfort
+        sensor comfort red 0.7
+        sensor comfort blue -0.3
+        var hot
+        sensor hot flame 1";
+
+    let result = parse_file(input).expect("Failed to parse sensors");
+
+    let sensors = &result.species[0].sensors;
+    assert_eq!(sensors.len(), 2);
+
+    // les deux lignes sur `comfort` forment un seul capteur
+    assert_eq!(sensors[0].name, "comfort");
+    assert_eq!(sensors[0].fields.len(), 2);
+    assert_eq!(sensors[0].fields[0].field, "red");
+    assert_eq!(sensors[0].fields[0].sensitivity, 0.7);
+    assert_eq!(sensors[0].fields[1].field, "blue");
+    assert_eq!(sensors[0].fields[1].sensitivity, -0.3);
+
+    assert_eq!(sensors[1].name, "hot");
+    assert_eq!(sensors[1].fields.len(), 1);
+    assert_eq!(sensors[1].fields[0].field, "flame");
+    assert_eq!(sensors[1].fields[0].sensitivity, 1.0);
+
+    // aucun argument ne doit être relu comme une commande
+    assert_eq!(result.species[0].vars.len(), 2);
+}
+
+#[test]
+fn test_parse_block_declaration() {
+    let input = r"
+        mineral rock 777
+        block smell
+        block light";
+
+    let result = parse_file(input).expect("Failed to parse block");
+
+    assert_eq!(result.species[0].blocks, vec!["smell", "light"]);
+}
+
+#[test]
+fn test_parse_seed() {
+    let result = parse_file("world 4 4 FFF\nseed 42").expect("Failed to parse seed");
+    assert_eq!(result.seed, Some(42));
+
+    let result = parse_file("world 4 4 FFF").expect("Failed to parse world");
+    assert_eq!(result.seed, None);
+}
+
+#[test]
+fn test_parse_update_mode() {
+    let result = parse_file("world 4 4 FFF\nmode async").expect("Failed to parse mode");
+    assert_eq!(result.mode, Some(UpdateMode::Async));
+
+    let result = parse_file("mode sync").expect("Failed to parse mode");
+    assert_eq!(result.mode, Some(UpdateMode::Sync));
+    assert_eq!(UpdateMode::default(), UpdateMode::Sync);
+}
+
+#[test]
+fn test_parse_field_metric_and_decay() {
+    let input = r"
+        mineral lamp FF0
+        var light 9
+        field light -1 euclid gauss
+        var gas 4
+        field gas -2 manhattan
+        var heat 3
+        field heat -1 inverse-square
+        var smell 2
+        field smell -1";
+    let result = parse_file(input).expect("Failed to parse fields");
+
+    let fields = &result.species[0].fields;
+    assert_eq!((fields[0].metric, fields[0].decay), (Metric::Euclidean, Decay::Gaussian));
+    assert_eq!((fields[1].metric, fields[1].decay), (Metric::Manhattan, Decay::Linear));
+    assert_eq!((fields[2].metric, fields[2].decay), (Metric::Chebyshev, Decay::InverseSquare));
+    assert_eq!((fields[3].metric, fields[3].decay), (Metric::Chebyshev, Decay::Linear));
+    assert_eq!(fields[1].step, -2);
+}
+
+#[test]
+fn test_parse_persistent_field() {
+    let input = r"
+        animal ant 000
+        var pheromone 5
+        field pheromone -5 persist 0.2 0.05
+        var smell 1
+        field smell -1 manhattan exp persist 1 0";
+    let result = parse_file(input).expect("Failed to parse persistent fields");
+
+    let fields = &result.species[0].fields;
+    assert_eq!(fields[0].kind, FieldKind::Persistent { diffusion: 0.2, evaporation: 0.05 });
+    assert_eq!(fields[1].kind, FieldKind::Persistent { diffusion: 1.0, evaporation: 0.0 });
+    assert_eq!((fields[1].metric, fields[1].decay), (Metric::Manhattan, Decay::Exponential));
+
+    let result = parse_file("mineral lamp FF0\nvar light 3\nfield light -1").expect("Failed to parse field");
+    assert_eq!(result.species[0].fields[0].kind, FieldKind::Instant);
+}
+
+#[test]
+fn test_compare_op_eval() {
+    assert!(CompareOp::Less.eval(1, 2));
+    assert!(!CompareOp::Less.eval(2, 2));
+    assert!(CompareOp::Greater.eval(3, 2));
+    assert!(!CompareOp::Greater.eval(2, 2));
+    assert!(CompareOp::Equal.eval(2, 2));
+    assert!(!CompareOp::Equal.eval(1, 2));
+    assert!(CompareOp::NotEqual.eval(1, 2));
+    assert!(!CompareOp::NotEqual.eval(2, 2));
+}
+
 #[test]
 fn test_parse_multiple_species() {
     let input = r"
@@ -77,6 +77,15 @@ fn test_tokenize_symbols() {
     assert_eq!(result[6].kind, TokenKind::Eol);
 }
 
+#[test]
+fn test_tokenize_equality_symbols() {
+    let input = "status n == 3 a != 2";
+    let result = tokenizer::tokenize(input).expect("Failed to tokenize equality symbols");
+
+    assert_eq!(result[2].kind, TokenKind::Symbol("==".to_string()));
+    assert_eq!(result[5].kind, TokenKind::Symbol("!=".to_string()));
+}
+
 #[test]
 fn test_tokenize_multiline() {
     let input = "world 16 16 000\nmineral wire FF0";